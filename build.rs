@@ -0,0 +1,44 @@
+//! Generates the registry of solutions that is linked into the main binary.
+//! Every `src/bin/YYYY-NN.rs` file is included as a module, and its `DaySolution` is added to the `SOLUTIONS` table.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut puzzles: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let (year, day) = name.strip_suffix(".rs")?.split_once('-')?;
+            let is_digits = |s: &str, len| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+            (is_digits(year, 4) && is_digits(day, 2)).then(|| (year.to_string(), day.to_string()))
+        })
+        .collect();
+
+    puzzles.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(format!("{year}-{day}.rs"));
+        // solutions are linted and tested as standalone binaries, don't repeat that here.
+        modules.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(warnings, clippy::all, clippy::pedantic)]\n#[path = {:?}]\nmod y{year}_d{day};\n\n",
+            path.display().to_string()
+        ));
+        entries.push_str(&format!(
+            "    #[cfg(not(test))]\n    &y{year}_d{day}::DaySolution,\n"
+        ));
+    }
+
+    let registry = format!(
+        "// @generated by build.rs, do not edit.\n\n{modules}/// All solutions present in `src/bin`, ordered by year and day.\npub static SOLUTIONS: &[&dyn advent_of_code::template::Solution] = &[\n{entries}];\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
@@ -10,7 +10,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
@@ -112,13 +112,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2286));
     }
 }
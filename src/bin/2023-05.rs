@@ -10,44 +10,44 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5, parse);
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Maps,
+}
+
+pub fn parse(input: &str) -> Almanac {
     let input = parse_input(input).unwrap().1;
 
-    let seeds = input.0;
-    let maps = Maps {
-        se2so: into_hmap(input.1),
-        so2fe: into_hmap(input.2),
-        fe2wa: into_hmap(input.3),
-        wa2li: into_hmap(input.4),
-        li2te: into_hmap(input.5),
-        te2hu: into_hmap(input.6),
-        hu2lo: into_hmap(input.7),
-    };
+    Almanac {
+        seeds: input.0,
+        maps: Maps {
+            se2so: into_hmap(input.1),
+            so2fe: into_hmap(input.2),
+            fe2wa: into_hmap(input.3),
+            wa2li: into_hmap(input.4),
+            li2te: into_hmap(input.5),
+            te2hu: into_hmap(input.6),
+            hu2lo: into_hmap(input.7),
+        },
+    }
+}
+
+pub fn part_one(almanac: &Almanac) -> Option<u64> {
+    let Almanac { seeds, maps } = almanac;
 
     seeds
-        .into_iter()
-        .map(|seed| {
+        .iter()
+        .map(|&seed| {
             // dbg!(seed);
             maps.location(seed).0
         })
         .min()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let input = parse_input(input).unwrap().1;
-
-    let seeds = input.0;
-    let maps = Maps {
-        se2so: into_hmap(input.1),
-        so2fe: into_hmap(input.2),
-        fe2wa: into_hmap(input.3),
-        wa2li: into_hmap(input.4),
-        li2te: into_hmap(input.5),
-        te2hu: into_hmap(input.6),
-        hu2lo: into_hmap(input.7),
-    };
+pub fn part_two(almanac: &Almanac) -> Option<u64> {
+    let Almanac { seeds, maps } = almanac;
 
     let mut min = u64::MAX;
     for s in seeds.chunks(2) {
@@ -240,13 +240,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(46));
     }
 }
@@ -15,7 +15,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 const START_NODE: Node = Node(('A', 'A', 'A'));
 const END_NODE: Node = Node(('Z', 'Z', 'Z'));
@@ -168,14 +168,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(6));
     }
@@ -8,7 +8,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10);
 
 pub fn part_one(input: &str) -> Option<u32> {
     use Direction::*;
@@ -436,14 +436,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(4));
     }
@@ -451,7 +451,7 @@ mod tests {
     #[test]
     fn test_part_two_2() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
+            "examples", PUZZLE, 3,
         ));
         assert_eq!(result, Some(8));
     }
@@ -459,7 +459,7 @@ mod tests {
     #[test]
     fn test_part_two_3() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
+            "examples", PUZZLE, 4,
         ));
         assert_eq!(result, Some(10));
     }
@@ -9,7 +9,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2023, 12);
 
 pub fn part_one(input: &str) -> Option<u64> {
     fn is_valid(springs: &[Kind], num: &[u64]) -> bool {
@@ -117,13 +117,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
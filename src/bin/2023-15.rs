@@ -11,7 +11,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2023, 15);
 
 pub fn part_one(input: &str) -> Option<u64> {
     Some(
@@ -134,13 +134,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1320));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(145));
     }
 }
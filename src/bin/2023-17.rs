@@ -6,7 +6,7 @@ use std::{
 use advent_of_code::common::*;
 use itertools::Itertools;
 use nom::{character::complete::anychar, combinator::map_res};
-advent_of_code::solution!(17);
+advent_of_code::solution!(2023, 17);
 
 const DIRS: [Direction; 4] = [
     Direction::Top,
@@ -244,20 +244,20 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(102));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(94));
     }
 
     #[test]
     fn test_part_two_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(71));
     }
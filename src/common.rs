@@ -1,4 +1,5 @@
 use std::{
+    error::Error,
     fmt::Display,
     ops::{Deref, DerefMut, Index, IndexMut},
 };
@@ -7,7 +8,7 @@ use nom::{
     character::complete::{newline, u64},
     combinator::map,
     multi::{many1, separated_list1},
-    IResult, Parser,
+    IResult,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -189,3 +190,171 @@ impl Pos {
         self.x == left
     }
 }
+
+/// Runs a parser over the whole input. Trailing whitespace, like the final newline of an input file, is ignored.
+/// Failures and unparsed input are reported as a [`ParseError`] that points at the offending position.
+///
+/// ```
+/// # use advent_of_code::common::parse_all;
+/// use nom::{character::complete::{newline, u32}, multi::separated_list1};
+///
+/// assert_eq!(parse_all(separated_list1(newline, u32), "1\n2\n").unwrap(), vec![1, 2]);
+/// let error = parse_all(separated_list1(newline, u32), "1\n2x\n").unwrap_err();
+/// let location = error.location.unwrap();
+/// assert_eq!((location.line, location.column), (2, 2));
+/// ```
+pub fn parse_all<'a, O>(
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+    input: &'a str,
+) -> Result<O, ParseError> {
+    match parser(input) {
+        Ok((rest, _)) if !rest.trim_end().is_empty() => {
+            // point at the unparsed input itself rather than at the whitespace before it.
+            Err(ParseError::new(
+                input,
+                rest.trim_start(),
+                "unexpected trailing input",
+            ))
+        }
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(
+            input,
+            e.input,
+            &format!("failed to parse ({})", e.code.description()),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// A parse failure of [`parse_all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Where the failure occurred, [`None`] if the parser failed on a string that is not part of the input.
+    pub location: Option<Location>,
+}
+
+/// A position in the input, by its 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// The line of the input the failure occurred in.
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// `rest` is the remaining input at the failure. Usually that is a suffix of `input`, but parsers such as
+    /// `map_parser` fail on a slice in the middle of it, and a parser may also fail on a string of its own.
+    fn new(input: &str, rest: &str, message: &str) -> Self {
+        Self {
+            message: message.into(),
+            location: Location::find(input, rest),
+        }
+    }
+}
+
+impl Location {
+    /// Locates the start of `rest` in `input`, [`None`] if `rest` is not a slice of `input`.
+    fn find(input: &str, rest: &str) -> Option<Self> {
+        let offset = (rest.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+
+        if offset + rest.len() > input.len() || !input.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Some(Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end].to_string(),
+        })
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(location) = &self.location else {
+            return f.write_str(&self.message);
+        };
+
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        writeln!(
+            f,
+            "{} at line {}, column {}",
+            self.message, location.line, location.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", location.source_line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(location.column - 1))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::{
+        bytes::complete::{tag, take},
+        character::complete::{newline, u32},
+        combinator::map_parser,
+        error::{Error, ErrorKind},
+        multi::separated_list1,
+        sequence::{pair, preceded},
+    };
+
+    use super::parse_all;
+
+    #[test]
+    fn reports_parse_errors() {
+        let parser = || separated_list1(newline, preceded(tag("Game "), u32));
+
+        assert_eq!(parse_all(parser(), "Game 1\nGame 2\n"), Ok(vec![1, 2]));
+
+        let error = parse_all(parser(), "Game 1\nGame x\n").unwrap_err();
+        let location = error.location.unwrap();
+        assert_eq!((location.line, location.column), (2, 1));
+        assert_eq!(error.message, "unexpected trailing input");
+
+        let error = parse_all(preceded(tag("Game "), u32), "Game x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse (Digit) at line 1, column 6\n  |\n1 | Game x\n  |      ^"
+        );
+    }
+
+    #[test]
+    fn locates_errors_inside_the_input() {
+        let column = |error: super::ParseError| error.location.map(|location| location.column);
+
+        // columns count characters, not bytes.
+        let error = parse_all(preceded(tag("äö: "), u32), "äö: x").unwrap_err();
+        assert_eq!(column(error), Some(5));
+
+        // the inner parser fails on a slice in the middle of the input, not on a suffix of it.
+        let parser = preceded(
+            pair(u32, newline),
+            map_parser(take(2usize), preceded(tag("a"), u32)),
+        );
+        let error = parse_all(parser, "12\nabc").unwrap_err();
+        let location = error.location.unwrap();
+        assert_eq!((location.line, location.column), (2, 2));
+
+        let elsewhere = |_: &str| -> nom::IResult<&str, u32> {
+            Err(nom::Err::Error(Error::new("elsewhere", ErrorKind::Tag)))
+        };
+        let error = parse_all(elsewhere, "input").unwrap_err();
+        assert_eq!(error.location, None);
+        assert_eq!(error.to_string(), "failed to parse (Tag)");
+    }
+}
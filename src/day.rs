@@ -114,6 +114,107 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days, used to select the days a batch command runs.
+///
+/// # Parsing
+/// A set is a comma-separated list of days and inclusive ranges of days.
+/// Either side of a range can be omitted, it then extends to the 1st or the 25th.
+///
+/// ```
+/// # use advent_of_code::{Day, DaySet};
+/// let days: DaySet = "1-3,7,24-".parse().unwrap();
+/// let days: Vec<u8> = days.iter().map(Day::into_inner).collect();
+/// assert_eq!(days, vec![1, 2, 3, 7, 24, 25])
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// A set that contains every day of advent.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    pub fn is_all(&self) -> bool {
+        *self == Self::all()
+    }
+
+    /// Iterates the days of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|&day| self.contains(day))
+    }
+}
+
+impl Default for DaySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        Self(iter.into_iter().fold(0, |set, day| set | (1 << day.0)))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_bound = |bound: &str, default: Day| match bound.trim() {
+            "" => Ok(default),
+            bound => bound.parse::<Day>(),
+        };
+
+        let mut days = vec![];
+
+        for segment in s.split(',') {
+            let error = || DaySetFromStrError(segment.trim().to_string());
+
+            let (first, last) = match segment.split_once('-') {
+                Some((first, last)) => (
+                    parse_bound(first, Day(1)).map_err(|_| error())?,
+                    parse_bound(last, Day(25)).map_err(|_| error())?,
+                ),
+                None => {
+                    let day = segment.trim().parse::<Day>().map_err(|_| error())?;
+                    (day, day)
+                }
+            };
+
+            if first > last {
+                return Err(error());
+            }
+
+            days.extend((first.0..=last.0).map(Day));
+        }
+
+        Ok(days.into_iter().collect())
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`], contains the invalid segment.
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid days `{}`, expecting days between 1 and 25 or ranges like `1-5` and `10-`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +235,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +268,26 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_set() {
+        let days = |s: &str| -> Vec<u8> {
+            let set: DaySet = s.parse().unwrap();
+            set.iter().map(Day::into_inner).collect()
+        };
+
+        assert_eq!(
+            days("1-5,7,20-"),
+            vec![1, 2, 3, 4, 5, 7, 20, 21, 22, 23, 24, 25]
+        );
+        assert_eq!(days("-2, 24"), vec![1, 2, 24]);
+        assert_eq!(days("3,3,2-3"), vec![2, 3]);
+        assert!("-".parse::<DaySet>().unwrap().is_all());
+        assert!("".parse::<DaySet>().is_err());
+        assert!("5-3".parse::<DaySet>().is_err());
+        assert!("1,26".parse::<DaySet>().is_err());
+        assert!("a-b".parse::<DaySet>().is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -1,5 +1,9 @@
 pub mod common;
 mod day;
+mod part;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use part::*;
+pub use year::*;
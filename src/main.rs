@@ -1,55 +1,163 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use std::{env, process};
+
+use advent_of_code::template::allocations;
+use advent_of_code::template::commands::{
+    all, bench, download, examples, inputs, read, scaffold, solve, verify, watch,
+};
 use args::{parse, AppArguments};
 
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            commands::{bench, examples},
+            runner::{self, RunOptions},
+        },
+        Day, DaySet, PuzzleId, Year,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            indices: Option<Vec<usize>>,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
+            refresh_tests: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
+            release: bool,
+            options: RunOptions,
+        },
+        Inputs {
+            puzzle: PuzzleId,
             release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: RunOptions,
         },
         All {
+            year: Year,
+            days: DaySet,
+            release: bool,
+            jobs: usize,
+            options: RunOptions,
+        },
+        Verify {
+            year: Year,
+            days: DaySet,
+            release: bool,
+        },
+        BenchCompare {
+            year: Year,
+            threshold: f64,
+        },
+        Watch {
+            puzzle: PuzzleId,
+            solve: bool,
             release: bool,
-            time: bool,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
+
+        // every subcommand accepts `--year`, it has to be parsed before the free-standing day argument.
+        let year: Year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::current);
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let mut puzzle = || -> Result<PuzzleId, pico_args::Error> {
+            let day: Day = args.free_from_str()?;
+            Ok(PuzzleId::new(year, day))
+        };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
+                days: args.opt_value_from_str("--days")?.unwrap_or_default(),
                 release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                options: RunOptions {
+                    timeout: runner::parse_timeout(&mut args)?,
+                    alloc: args.contains("--alloc"),
+                    time: args.contains("--time"),
+                    warmup: args.opt_value_from_str("--warmup")?,
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    verify: true,
+                    ..RunOptions::default()
+                },
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("download") => AppArguments::Download { puzzle: puzzle()? },
+            Some("read") => AppArguments::Read { puzzle: puzzle()? },
+            Some("examples") => AppArguments::Examples {
+                puzzle: puzzle()?,
+                indices: args.opt_value_from_fn("--index", examples::parse_indices)?,
+                overwrite: args.contains("--overwrite"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle()?,
+                refresh_tests: args.contains("--refresh-tests"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: puzzle()?,
+                release: args.contains("--release"),
+                options: RunOptions {
+                    input: runner::parse_input(&mut args)?,
+                    part: args.opt_value_from_str("--part")?,
+                    timeout: runner::parse_timeout(&mut args)?,
+                    alloc: args.contains("--alloc"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    force: args.contains("--force"),
+                    time: args.contains("--time"),
+                    warmup: args.opt_value_from_str("--warmup")?,
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    verify: true,
+                    ..RunOptions::default()
+                },
+            },
+            Some("inputs") => AppArguments::Inputs {
+                puzzle: puzzle()?,
+                release: args.contains("--release"),
+                options: RunOptions {
+                    part: args.opt_value_from_str("--part")?,
+                    timeout: runner::parse_timeout(&mut args)?,
+                    ..RunOptions::default()
+                },
+            },
+            Some("verify") => AppArguments::Verify {
+                year,
+                days: args.opt_value_from_str("--days")?.unwrap_or_default(),
+                release: args.contains("--release"),
+            },
+            Some("watch") => AppArguments::Watch {
+                puzzle: puzzle()?,
+                solve: args.contains("--solve"),
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            },
+            Some("bench") => match args.free_from_str::<String>()?.as_str() {
+                "compare" => AppArguments::BenchCompare {
+                    year,
+                    threshold: args
+                        .opt_value_from_str("--threshold")?
+                        .unwrap_or(bench::DEFAULT_THRESHOLD),
+                },
+                x => {
+                    eprintln!("Unknown bench command: {x}");
+                    process::exit(1);
+                }
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -61,6 +169,16 @@ mod args {
             }
         };
 
+        if let AppArguments::Solve {
+            puzzle, options, ..
+        }
+        | AppArguments::Inputs {
+            puzzle, options, ..
+        } = &app_args
+        {
+            check_parts(*puzzle, options);
+        }
+
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
@@ -68,25 +186,122 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Exits if `--part` or `--submit` select a part the puzzle does not have, or if they contradict each other.
+    fn check_parts(puzzle: PuzzleId, options: &RunOptions) {
+        for part in [options.part, options.submit].into_iter().flatten() {
+            if !part.exists_on(puzzle.day) {
+                eprintln!("Day {} only has one part.", puzzle.day);
+                process::exit(1);
+            }
+        }
+
+        if let (Some(part), Some(submit)) = (options.part, options.submit) {
+            if part != submit {
+                eprintln!("Can't submit part {submit} when only part {part} is run.");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Solutions are linked into this binary, so it has to be built with the profile requested via `--release`,
+/// and with the counting allocator if `--alloc` is passed.
+/// If it is not, the same command is re-run through cargo with the matching profile and features.
+fn ensure_profile(release: bool, alloc: bool) {
+    const REEXEC_VAR: &str = "AOC_PROFILE_REEXEC";
+
+    // `debug_assertions` are disabled in release builds.
+    let profile_matches = release != cfg!(debug_assertions);
+    let features_match = !alloc || allocations::is_enabled();
+
+    if (profile_matches && features_match) || env::var_os(REEXEC_VAR).is_some() {
+        return;
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--quiet".to_string()];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    if alloc {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("alloc".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.extend(env::args().skip(1));
+
+    let status = process::Command::new("cargo")
+        .args(&cmd_args)
+        .env(REEXEC_VAR, "1")
+        .status()
+        .unwrap();
+
+    process::exit(status.code().unwrap_or(1));
 }
 
 fn main() {
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                days,
+                release,
+                jobs,
+                options,
+            } => {
+                ensure_profile(release, options.alloc);
+                all::handle(solutions::SOLUTIONS, year, days, release, jobs, &options);
+            }
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples {
+                puzzle,
+                indices,
+                overwrite,
+            } => examples::handle(puzzle, indices, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
+                refresh_tests,
+            } => scaffold::handle(puzzle, refresh_tests),
             AppArguments::Solve {
-                day,
+                puzzle,
+                release,
+                options,
+            } => {
+                ensure_profile(release, options.alloc);
+                solve::handle(solutions::SOLUTIONS, puzzle, release, &options);
+            }
+            AppArguments::Inputs {
+                puzzle,
+                release,
+                options,
+            } => {
+                ensure_profile(release, false);
+                inputs::handle(solutions::SOLUTIONS, puzzle, &options);
+            }
+            AppArguments::Verify {
+                year,
+                days,
+                release,
+            } => {
+                ensure_profile(release, false);
+                verify::handle(solutions::SOLUTIONS, year, days);
+            }
+            AppArguments::BenchCompare { year, threshold } => {
+                bench::handle_compare(year, threshold);
+            }
+            AppArguments::Watch {
+                puzzle,
+                solve,
                 release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+            } => watch::handle(puzzle, solve, release),
         },
     };
 }
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::Day;
+
+/// A part of a puzzle (i.e. either 1 or 2).
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::Part;
+/// assert_eq!(Part::TWO.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Part(u8);
+
+impl Part {
+    pub const ONE: Self = Self(1);
+    pub const TWO: Self = Self(2);
+
+    /// Creates a [`Part`] from the provided value if it's 1 or 2,
+    /// returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 | 2 => Some(Self(part)),
+            _ => None,
+        }
+    }
+
+    /// Both parts in order.
+    pub fn both() -> [Self; 2] {
+        [Self::ONE, Self::TWO]
+    }
+
+    /// Converts the [`Part`] into an [`u8`].
+    pub fn into_inner(self) -> u8 {
+        self.0
+    }
+
+    /// Whether a day has this part. Day 25 only has a first part.
+    pub fn exists_on(self, day: Day) -> bool {
+        self == Self::ONE || day != 25
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number of 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Part;
+    use crate::day;
+
+    #[test]
+    fn parses_part() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::ONE);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::TWO);
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn day_25_has_one_part() {
+        assert!(Part::TWO.exists_on(day!(24)));
+        assert!(Part::ONE.exists_on(day!(25)));
+        assert!(!Part::TWO.exists_on(day!(25)));
+    }
+}
@@ -0,0 +1,189 @@
+/// Heap allocation profiling of solution parts.
+/// With the `alloc` feature enabled, the library installs [`CountingAllocator`] as the global allocator.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+
+#[cfg(feature = "alloc")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Allocations made while running a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Number of allocations, growing or shrinking an allocation counts as one.
+    pub allocations: u64,
+    /// Total bytes requested by these allocations.
+    pub bytes: u64,
+    /// Highest amount of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocation{} · {} allocated · {} peak",
+            self.allocations,
+            if self.allocations == 1 { "" } else { "s" },
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes),
+        )
+    }
+}
+
+/// Counters are kept per thread, so that allocations of the runner or of days running in parallel
+/// are not attributed to the measured part.
+struct Counters {
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    live: Cell<u64>,
+    peak: Cell<u64>,
+}
+
+thread_local! {
+    // const-initialized without a destructor, so accessing it never allocates.
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// A global allocator that forwards to the system allocator and counts the allocations of each thread.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record(allocated: usize, freed: usize) {
+        // the thread-local is gone while a thread shuts down, these allocations are not counted.
+        let _ = COUNTERS.try_with(|counters| {
+            if allocated > 0 {
+                counters.allocations.set(counters.allocations.get() + 1);
+                counters.bytes.set(counters.bytes.get() + allocated as u64);
+            }
+
+            // memory may be freed by a different thread than the one that allocated it.
+            let live = (counters.live.get() + allocated as u64).saturating_sub(freed as u64);
+            counters.live.set(live);
+            counters.peak.set(counters.peak.get().max(live));
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Whether the library was built with the counting allocator, i.e. with the `alloc` feature.
+#[must_use]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc")
+}
+
+/// Runs a function and counts the allocations it makes on the current thread.
+/// The counts are all zero if the counting allocator is not enabled.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    COUNTERS.with(|counters| {
+        counters.allocations.set(0);
+        counters.bytes.set(0);
+        counters.live.set(0);
+        counters.peak.set(0);
+    });
+
+    let result = func();
+
+    let stats = COUNTERS.with(|counters| AllocStats {
+        allocations: counters.allocations.get(),
+        bytes: counters.bytes.get(),
+        peak_bytes: counters.peak.get(),
+    });
+
+    (result, stats)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_alloc_stats() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+
+        let stats = AllocStats {
+            allocations: 1,
+            bytes: 2048,
+            peak_bytes: 1024,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "1 allocation · 2.0 KiB allocated · 1.0 KiB peak"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = super::measure(|| {
+            let mut v: Vec<u8> = Vec::with_capacity(100);
+            v.extend_from_slice(&[0; 100]);
+            drop(v);
+            vec![0u8; 50]
+        });
+
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 150);
+        assert_eq!(stats.peak_bytes, 100);
+    }
+}
@@ -0,0 +1,73 @@
+/// Store of accepted answers, used to verify that solutions still produce them.
+/// Answers live in `data/<year>/answers/<day>-<part>.txt`, following the naming of `read_file_part`.
+/// Answers of additional inputs `data/<year>/inputs/<day>/<name>.txt` live in `data/<year>/answers/<day>/<name>-<part>.txt`.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::get_data_path;
+use crate::{Part, PuzzleId};
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId, part: Part) -> PathBuf {
+    get_data_path(
+        puzzle.year,
+        "answers",
+        &format!("{}-{part}.txt", puzzle.day),
+    )
+}
+
+#[must_use]
+pub fn get_path_for_input(puzzle: PuzzleId, name: &str, part: Part) -> PathBuf {
+    get_data_path(
+        puzzle.year,
+        "answers",
+        &format!("{}/{name}-{part}.txt", puzzle.day),
+    )
+}
+
+/// Returns the accepted answer of a part, or [`None`] if it is not known yet.
+#[must_use]
+pub fn load(puzzle: PuzzleId, part: Part) -> Option<String> {
+    read_answer(get_path(puzzle, part))
+}
+
+/// Returns the answer of a part for the additional input `name`, or [`None`] if there is none.
+#[must_use]
+pub fn load_for_input(puzzle: PuzzleId, name: &str, part: Part) -> Option<String> {
+    read_answer(get_path_for_input(puzzle, name, part))
+}
+
+fn read_answer(path: PathBuf) -> Option<String> {
+    let answer = fs::read_to_string(path).ok()?;
+    let answer = answer.trim_end();
+    (!answer.is_empty()).then(|| answer.to_string())
+}
+
+/// Stores the accepted answer of a part, replacing a previously stored answer.
+pub fn save(puzzle: PuzzleId, part: Part, answer: &str) -> io::Result<()> {
+    let path = get_path(puzzle, part);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, format!("{}\n", answer.trim_end()))
+}
+
+/// Checks whether an answer matches an accepted answer. Trailing whitespace is ignored.
+#[must_use]
+pub fn matches(answer: &str, expected: &str) -> bool {
+    answer.trim_end() == expected.trim_end()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::matches;
+
+    #[test]
+    fn ignores_trailing_whitespace() {
+        assert!(matches("42", "42\n"));
+        assert!(matches("#..#\n.##.\n", "#..#\n.##."));
+        assert!(!matches("42", "43"));
+        assert!(!matches(" 42", "42"));
+    }
+}
@@ -0,0 +1,409 @@
+/// Built-in client for the Advent of Code website, an alternative to the external aoc-cli tool.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::get_data_path;
+use crate::{Part, PuzzleId};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in `AOC_SESSION` or in a session file.
+    MissingSession,
+    Request(Box<ureq::Error>),
+    /// The page did not contain the expected content, e.g. because the puzzle is not unlocked yet.
+    UnexpectedResponse,
+    IoError(io::Error),
+}
+
+impl Error for AocClientError {}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create an \".adventofcode.session\" file in your home directory."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse => {
+                write!(f, "unexpected response from the Advent of Code website.")
+            }
+            AocClientError::IoError(e) => write!(f, "could not write output files: {e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Request(Box::new(e))
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+pub struct AocClient {
+    /// Defaults to `https://adventofcode.com`, can be pointed at a stand-in server for tests.
+    pub base_url: String,
+    pub session: String,
+}
+
+impl AocClient {
+    /// Configures a client from the environment:
+    /// - `AOC_SESSION`, or the contents of `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+    /// - `AOC_BASE_URL`, defaults to `https://adventofcode.com`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        Ok(Self {
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into()),
+            session: get_session().ok_or(AocClientError::MissingSession)?,
+        })
+    }
+
+    /// Fetches the input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        Ok(self.agent().get(&url).call()?.into_string()?)
+    }
+
+    /// Fetches the description of a puzzle, converted to markdown.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self
+            .agent()
+            .get(&self.puzzle_url(puzzle))
+            .call()?
+            .into_string()?;
+
+        let articles: Vec<String> =
+            find_elements(&html, "<article class=\"day-desc\">", "</article>")
+                .map(html_to_markdown)
+                .collect();
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse);
+        }
+
+        Ok(articles.join("\n\n") + "\n")
+    }
+
+    /// Submits an answer, returns the response message of the website as text.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: Part,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let html = self
+            .agent()
+            .post(&url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let response = find_elements(&html, "<article>", "</article>")
+            .next()
+            .map(html_to_markdown);
+
+        response.ok_or(AocClientError::UnexpectedResponse)
+    }
+
+    /// Downloads input and description of a puzzle to `data/<year>/inputs` and `data/<year>/puzzles`.
+    pub fn download(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let input_path = get_data_path(puzzle.year, "inputs", &format!("{}.txt", puzzle.day));
+        let puzzle_path = get_data_path(puzzle.year, "puzzles", &format!("{}.md", puzzle.day));
+
+        let input = self.input(puzzle)?;
+        let description = self.puzzle(puzzle)?;
+
+        write_file(&input_path, &input)?;
+        write_file(&puzzle_path, &description)?;
+
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        Ok(())
+    }
+
+    /// Prints the description of a puzzle and updates `data/<year>/puzzles`.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let description = self.puzzle(puzzle)?;
+        let puzzle_path = get_data_path(puzzle.year, "puzzles", &format!("{}.md", puzzle.day));
+        write_file(&puzzle_path, &description)?;
+        print!("{description}");
+        Ok(())
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url.trim_end_matches('/'),
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn agent(&self) -> AuthenticatedAgent {
+        AuthenticatedAgent {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            cookie: format!("session={}", self.session),
+        }
+    }
+}
+
+/// Adds the session cookie to every request.
+struct AuthenticatedAgent {
+    agent: ureq::Agent,
+    cookie: String,
+}
+
+impl AuthenticatedAgent {
+    fn get(&self, url: &str) -> ureq::Request {
+        self.agent.get(url).set("Cookie", &self.cookie)
+    }
+
+    fn post(&self, url: &str) -> ureq::Request {
+        self.agent.post(url).set("Cookie", &self.cookie)
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from)?;
+    let config = env::var_os("XDG_CONFIG_HOME").map_or_else(|| home.join(".config"), PathBuf::from);
+
+    [
+        home.join(".adventofcode.session"),
+        config.join("adventofcode.session"),
+    ]
+    .iter()
+    .filter_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .find(|session| !session.is_empty())
+}
+
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Iterates over the inner HTML of all elements starting with `open` and ending with `close`.
+fn find_elements<'a>(
+    html: &'a str,
+    open: &'a str,
+    close: &'a str,
+) -> impl Iterator<Item = &'a str> + 'a {
+    let mut rest = html;
+
+    std::iter::from_fn(move || {
+        let start = rest.find(open)? + open.len();
+        let len = rest[start..].find(close)?;
+        let inner = &rest[start..start + len];
+        rest = &rest[start + len + close.len()..];
+        Some(inner)
+    })
+}
+
+/// Converts the subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            out.push_str(&decode_entities(rest));
+            break;
+        };
+
+        let text = &rest[..tag_start];
+
+        // whitespace between block elements.
+        if in_pre || !text.trim().is_empty() || !text.contains('\n') {
+            out.push_str(&decode_entities(text));
+        }
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+
+        let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+
+        match (name, closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => end_block(&mut out),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                end_block(&mut out);
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(get_attribute(attributes, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+fn end_block(out: &mut String) {
+    out.truncate(out.trim_end_matches('\n').len());
+    out.push_str("\n\n");
+}
+
+fn get_attribute(attributes: &str, name: &str) -> Option<String> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = attributes[start..].find('"')?;
+    Some(decode_entities(&attributes[start..start + len]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::{find_elements, html_to_markdown, AocClient};
+    use crate::{day, year, Part, PuzzleId};
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = r#"<h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href="/2023/about">global snow production</a>.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul><li>In <code>1abc2</code>, the value is <code><em>12</em></code>.</li><li>A &lt;b&gt; &amp; c</li></ul>
+<p>What is the sum?</p>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Trebuchet?! ---\n\nSomething is *wrong* with [global snow production](/2023/about).\n\n```\n1abc2\npqr3stu8vwx\n```\n\n- In `1abc2`, the value is `*12*`.\n- A <b> & c\n\nWhat is the sum?"
+        );
+    }
+
+    #[test]
+    fn finds_elements() {
+        let html = r#"<main><article class="day-desc"><p>one</p></article><p>x</p><article class="day-desc"><p>two</p></article></main>"#;
+        let found: Vec<&str> =
+            find_elements(html, "<article class=\"day-desc\">", "</article>").collect();
+        assert_eq!(found, vec!["<p>one</p>", "<p>two</p>"]);
+    }
+
+    #[test]
+    fn submits_to_base_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = vec![];
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                head.push(line.trim_end().to_string());
+            }
+
+            let length: usize = head
+                .iter()
+                .find_map(|h| {
+                    h.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let page = "<html><main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main></html>";
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                page.len()
+            )
+            .unwrap();
+
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let client = AocClient {
+            base_url: format!("http://127.0.0.1:{port}"),
+            session: "abc".into(),
+        };
+
+        let response = client
+            .submit(PuzzleId::new(year!(2023), day!(5)), Part::TWO, "42")
+            .unwrap();
+        let (head, body) = server.join().unwrap();
+
+        assert_eq!(head[0], "POST /2023/day/5/answer HTTP/1.1");
+        assert!(head.iter().any(|h| h == "Cookie: session=abc"));
+        assert_eq!(body, "level=2&answer=42");
+        assert_eq!(
+            response,
+            "That's the right answer! You are *one gold star* closer."
+        );
+    }
+}
@@ -0,0 +1,114 @@
+/// Remote operations against the Advent of Code website.
+/// They are implemented by the external aoc-cli tool and by the built-in [`AocClient`].
+use std::{env, error::Error, fmt::Display};
+
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::{Part, PuzzleId};
+
+pub trait Backend {
+    /// Downloads input and description of a puzzle to `data/<year>/inputs` and `data/<year>/puzzles`.
+    fn download(&self, puzzle: PuzzleId) -> Result<(), BackendError>;
+
+    /// Prints the description of a puzzle.
+    fn read(&self, puzzle: PuzzleId) -> Result<(), BackendError>;
+
+    /// Submits an answer, returns the response message of the website.
+    fn submit(&self, puzzle: PuzzleId, part: Part, answer: &str) -> Result<String, BackendError>;
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    AocCli(AocCommandError),
+    Client(AocClientError),
+    /// `AOC_BACKEND` is set to an unknown value.
+    UnknownBackend(String),
+}
+
+impl Error for BackendError {}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            BackendError::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            BackendError::Client(e) => write!(f, "{e}"),
+            BackendError::UnknownBackend(name) => write!(
+                f,
+                "unknown backend \"{name}\" in AOC_BACKEND, expecting \"aoc-cli\" or \"native\"."
+            ),
+        }
+    }
+}
+
+impl From<AocCommandError> for BackendError {
+    fn from(e: AocCommandError) -> Self {
+        BackendError::AocCli(e)
+    }
+}
+
+impl From<AocClientError> for BackendError {
+    fn from(e: AocClientError) -> Self {
+        BackendError::Client(e)
+    }
+}
+
+/// Selects the backend named by `AOC_BACKEND` (`aoc-cli` or `native`).
+/// If it is not set, aoc-cli is used when it is installed and the built-in client otherwise.
+pub fn select() -> Result<Box<dyn Backend>, BackendError> {
+    match env::var("AOC_BACKEND").ok().as_deref() {
+        Some("aoc-cli") => {
+            aoc_cli::check()?;
+            Ok(Box::new(aoc_cli::AocCli))
+        }
+        Some("native") => Ok(Box::new(AocClient::from_env()?)),
+        Some(name) => Err(BackendError::UnknownBackend(name.into())),
+        None => match aoc_cli::check() {
+            Ok(()) => Ok(Box::new(aoc_cli::AocCli)),
+            Err(_) => Ok(Box::new(AocClient::from_env()?)),
+        },
+    }
+}
+
+impl Backend for AocClient {
+    fn download(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        Ok(AocClient::download(self, puzzle)?)
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        Ok(AocClient::read(self, puzzle)?)
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: Part, answer: &str) -> Result<String, BackendError> {
+        println!("Submitting result...");
+        let response = AocClient::submit(self, puzzle, part, answer)?;
+        println!("{response}");
+        Ok(response)
+    }
+}
+
+impl Backend for aoc_cli::AocCli {
+    fn download(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        aoc_cli::download(puzzle)?;
+        Ok(())
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        aoc_cli::read(puzzle)?;
+        Ok(())
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: Part, answer: &str) -> Result<String, BackendError> {
+        println!("Submitting result via aoc-cli...");
+        match aoc_cli::submit(puzzle, part, answer) {
+            // aoc-cli exits with an error for wrong answers, the response is still of interest.
+            Ok(output) | Err(AocCommandError::BadExitStatus(output)) => {
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
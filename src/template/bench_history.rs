@@ -0,0 +1,324 @@
+/// History of benchmark runs, used to detect solutions that got slower.
+/// Every `--release --time` run is appended to `data/<year>/benchmarks/history.ndjson` as a line of
+/// `{"timestamp":<unix timestamp>,"commit":<git commit hash>,"reports":[<benchmarked part reports>]}`.
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::report::{PartReport, Phase, ReportParseError};
+use crate::template::{datetime, get_data_path};
+use crate::{PuzzleId, Year};
+
+/// The benchmarked parts of a single run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRun {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The commit that was checked out, [`None`] outside of a git repository.
+    pub commit: Option<String>,
+    pub reports: Vec<PartReport>,
+}
+
+impl BenchmarkRun {
+    /// Creates a run of the current commit from the benchmarked reports in `reports`.
+    #[must_use]
+    pub fn new(reports: &[PartReport]) -> Self {
+        Self {
+            timestamp: datetime::now(),
+            commit: head_commit(),
+            reports: reports
+                .iter()
+                .filter(|report| report.stats.is_some() && report.error.is_none())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Serializes the run into a single-line JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let reports: Vec<String> = self.reports.iter().map(PartReport::to_json).collect();
+
+        format!(
+            r#"{{"timestamp":{},"commit":{},"reports":[{}]}}"#,
+            self.timestamp,
+            self.commit.as_ref().map_or_else(
+                || "null".into(),
+                // stringifying a plain string value can not fail.
+                |commit| JsonValue::String(commit.clone()).stringify().unwrap()
+            ),
+            reports.join(",")
+        )
+    }
+
+    /// Parses a run previously serialized with [`BenchmarkRun::to_json`].
+    pub fn from_json(s: &str) -> Result<Self, ReportParseError> {
+        let value: JsonValue = s.parse().map_err(|_| ReportParseError::Json)?;
+        let object: &HashMap<String, JsonValue> = value.get().ok_or(ReportParseError::Json)?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = match object.get("timestamp") {
+            Some(JsonValue::Number(n)) => *n as u64,
+            _ => return Err(ReportParseError::Field("timestamp")),
+        };
+
+        let commit = match object.get("commit") {
+            Some(JsonValue::String(s)) => Some(s.clone()),
+            Some(JsonValue::Null) | None => None,
+            Some(_) => return Err(ReportParseError::Field("commit")),
+        };
+
+        let reports = match object.get("reports") {
+            Some(JsonValue::Array(items)) => items
+                .iter()
+                .map(PartReport::from_json_value)
+                .collect::<Result<_, _>>()?,
+            _ => return Err(ReportParseError::Field("reports")),
+        };
+
+        Ok(Self {
+            timestamp,
+            commit,
+            reports,
+        })
+    }
+
+    /// The first seven characters of the commit hash, as shown by git.
+    #[must_use]
+    pub fn short_commit(&self) -> &str {
+        self.commit
+            .as_deref()
+            .map_or("unknown commit", |commit| &commit[..commit.len().min(7)])
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year) -> PathBuf {
+    get_data_path(year, "benchmarks", "history.ndjson")
+}
+
+/// Loads all runs of a year, oldest first. Malformed lines are skipped.
+#[must_use]
+pub fn load(year: Year) -> Vec<BenchmarkRun> {
+    fs::read_to_string(get_path(year))
+        .map(|s| {
+            s.lines()
+                .filter_map(|line| BenchmarkRun::from_json(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends a run to the history of a year. Runs without benchmarked parts are not stored.
+pub fn record(year: Year, run: &BenchmarkRun) -> io::Result<()> {
+    if run.reports.is_empty() {
+        return Ok(());
+    }
+
+    let path = get_path(year);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", run.to_json())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the hash of the checked out commit from the `.git` directory, without invoking git.
+#[must_use]
+pub fn head_commit() -> Option<String> {
+    let current_dir = env::current_dir().ok()?;
+    let mut git_dir = current_dir.join(".git");
+
+    // worktrees and submodules have a `.git` file that points to the actual directory, possibly relative to it.
+    if git_dir.is_file() {
+        let content = fs::read_to_string(&git_dir).ok()?;
+        git_dir = current_dir.join(content.strip_prefix("gitdir:")?.trim());
+    }
+
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let Some(reference) = head.trim().strip_prefix("ref:").map(str::trim) else {
+        // a detached HEAD contains the hash itself.
+        return Some(head.trim().to_string());
+    };
+
+    // the directory of a worktree only has its own HEAD, branches are stored in the directory of the main
+    // repository, which its `commondir` file points to.
+    let refs_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir,
+    };
+
+    if let Ok(hash) = fs::read_to_string(refs_dir.join(reference)) {
+        return Some(hash.trim().to_string());
+    }
+
+    // refs that were not updated since `git gc` only exist in `packed-refs`.
+    let packed = fs::read_to_string(refs_dir.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (hash, name) = line.split_once(' ')?;
+        (name == reference).then(|| hash.to_string())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change of a part's median time between its previous and its latest benchmark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub puzzle: PuzzleId,
+    pub phase: Phase,
+    pub baseline: Duration,
+    /// The commit of the run the baseline was taken from.
+    pub baseline_commit: String,
+    pub latest: Duration,
+    pub latest_commit: String,
+}
+
+impl Comparison {
+    /// The relative change in percent, positive if the part got slower.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_nanos().max(1) as f64;
+        (self.latest.as_nanos() as f64 - baseline) / baseline * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Compares the latest benchmark of every part against the benchmark before it, ordered by day and phase.
+/// Runs of `solve` only benchmark a single day, so the two benchmarks of a part may come from any run.
+/// Parts that were only benchmarked once are skipped.
+#[must_use]
+pub fn compare(runs: &[BenchmarkRun]) -> Vec<Comparison> {
+    let phase_order = |phase: Phase| match phase {
+        Phase::Parse => 0,
+        Phase::Part(part) => part.into_inner(),
+    };
+
+    // the latest two benchmarks of each part, as `(run, report)`.
+    let mut history: BTreeMap<(PuzzleId, u8), Vec<(&BenchmarkRun, &PartReport)>> = BTreeMap::new();
+
+    for run in runs {
+        for report in &run.reports {
+            let benchmarks = history
+                .entry((report.puzzle, phase_order(report.phase)))
+                .or_default();
+            benchmarks.push((run, report));
+            if benchmarks.len() > 2 {
+                benchmarks.remove(0);
+            }
+        }
+    }
+
+    history
+        .into_values()
+        .filter_map(|benchmarks| {
+            let [(baseline_run, baseline), (latest_run, latest)] = benchmarks[..] else {
+                return None;
+            };
+
+            Some(Comparison {
+                puzzle: latest.puzzle,
+                phase: latest.phase,
+                baseline: baseline.stats?.median,
+                baseline_commit: baseline_run.short_commit().to_string(),
+                latest: latest.stats?.median,
+                latest_commit: latest_run.short_commit().to_string(),
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, BenchmarkRun};
+    use crate::template::report::{PartReport, Phase};
+    use crate::template::stats::Stats;
+    use crate::{day, year, Part, PuzzleId};
+
+    fn report(day: u8, part: Part, nanos: u64) -> PartReport {
+        PartReport {
+            puzzle: PuzzleId::new(year!(2023), crate::Day::new(day).unwrap()),
+            phase: Phase::Part(part),
+            answer: Some("42".into()),
+            duration: Duration::from_nanos(nanos),
+            samples: 10,
+            stats: Stats::from_samples(&[Duration::from_nanos(nanos)]),
+            alloc: None,
+            expected: None,
+            error: None,
+            variants: vec![],
+        }
+    }
+
+    fn run(commit: &str, reports: Vec<PartReport>) -> BenchmarkRun {
+        BenchmarkRun {
+            timestamp: 1_701_752_400,
+            commit: Some(commit.into()),
+            reports,
+        }
+    }
+
+    #[test]
+    fn roundtrips_run() {
+        let run = run("77cfa9b3fe7f22ff", vec![report(5, Part::ONE, 1000)]);
+        assert_eq!(BenchmarkRun::from_json(&run.to_json()).unwrap(), run);
+        assert_eq!(run.short_commit(), "77cfa9b");
+    }
+
+    #[test]
+    fn compares_against_previous_benchmark() {
+        let runs = [
+            run(
+                "a",
+                vec![report(1, Part::ONE, 100), report(5, Part::ONE, 1000)],
+            ),
+            run("b", vec![report(1, Part::ONE, 200)]),
+            run(
+                "c",
+                vec![report(5, Part::ONE, 1500), report(5, Part::TWO, 10)],
+            ),
+            run("d", vec![report(1, Part::ONE, 210)]),
+        ];
+
+        let comparisons = compare(&runs);
+        assert_eq!(comparisons.len(), 2);
+
+        let day_1 = &comparisons[0];
+        assert_eq!(day_1.puzzle.day, day!(1));
+        assert_eq!(
+            (day_1.baseline_commit.as_str(), day_1.latest_commit.as_str()),
+            ("b", "d")
+        );
+        assert!((day_1.change() - 5.0).abs() < 1e-9);
+        assert!(!day_1.is_regression(10.0));
+
+        let day_5 = &comparisons[1];
+        assert_eq!(
+            (day_5.baseline_commit.as_str(), day_5.latest_commit.as_str()),
+            ("a", "c")
+        );
+        assert!(day_5.is_regression(10.0));
+        assert!(!day_5.is_regression(50.0));
+
+        assert!(compare(&runs[..1]).is_empty());
+    }
+}
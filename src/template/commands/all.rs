@@ -1,254 +1,292 @@
-use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
 
+use crate::template::commands::bench;
 use crate::template::{
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    report::{Format, PartReport, Phase},
+    runner::{self, RunOptions},
+    solution, worker, Solution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::{Day, DaySet, Part, PuzzleId, Year};
+
+pub fn handle(
+    solutions: &[&'static dyn Solution],
+    year: Year,
+    days: DaySet,
+    is_release: bool,
+    jobs: usize,
+    options: &RunOptions,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let mut reports: Vec<PartReport> = vec![];
+    let format = options.format;
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+    let mut collect = |puzzle: PuzzleId, output: Vec<PartReport>| {
+        if output.is_empty() {
+            if format == Format::Text {
+                println!("Not solved.");
+            }
+        } else {
+            timings.push(collect_timings(&output, puzzle));
+            reports.extend(output);
         }
+    };
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
-
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // benchmarks of concurrently running days would influence each other.
+    if jobs > 1 && !options.time {
+        let puzzles = days.iter().map(|day| PuzzleId::new(year, day)).collect();
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        run_parallel(solutions, puzzles, jobs, options, |puzzle, output| {
+            print_header(puzzle.day, days, format);
+            output
+                .iter()
+                .for_each(|report| runner::emit_report(report, format));
+            collect(puzzle, output);
+        });
+    } else {
+        if jobs > 1 {
+            eprintln!("Note: running days serially to keep timings accurate.");
         }
-    });
 
-    if is_timed {
+        days.iter().for_each(|day| {
+            let puzzle = PuzzleId::new(year, day);
+            print_header(day, days, format);
+
+            let output = solution::find(solutions, puzzle)
+                .map(|solution| solution::run(solution, options))
+                .unwrap_or_default();
+
+            collect(puzzle, output);
+        });
+    }
+
+    runner::finish(&reports, options);
+
+    if options.time && is_release {
+        bench::record(year, &reports);
+    }
+
+    if options.time {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if format == Format::Text {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
-        if is_release {
+        // the table lists every day, it can't be updated from a partial run.
+        if is_release && !days.is_all() {
+            eprintln!("Skipped updating README with benchmarks, not all days were run.");
+        } else if is_release && worker::has_abandoned() {
+            eprintln!("Skipped updating README with benchmarks, a part that timed out kept running and skewed the timings.");
+        } else if is_release {
             match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+                Ok(()) => eprintln!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+    runner::exit_on_failure(&reports);
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
+fn print_header(day: Day, days: DaySet, format: Format) {
+    if format == Format::Text {
+        if days.iter().next() != Some(day) {
+            println!();
+        }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Runs the given puzzles on a pool of `jobs` worker threads.
+/// Workers don't print, instead `on_done` is called for every puzzle in order as soon as it and all previous puzzles finished.
+fn run_parallel(
+    solutions: &[&'static dyn Solution],
+    puzzles: Vec<PuzzleId>,
+    jobs: usize,
+    options: &RunOptions,
+    mut on_done: impl FnMut(PuzzleId, Vec<PartReport>),
+) {
+    let next = AtomicUsize::new(0);
+    let options = RunOptions {
+        quiet: true,
+        ..options.clone()
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
 
-        if is_release {
-            args.push("--release");
-        }
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (puzzles, next, options) = (&puzzles, &next, &options);
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&puzzle) = puzzles.get(index) else {
+                    break;
+                };
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+                let output = solution::find(solutions, puzzle)
+                    .map(|solution| solution::run(solution, options))
+                    .unwrap_or_default();
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+                if sender.send((index, output)).is_err() {
+                    break;
+                }
+            });
+        }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        // drop the original sender so the receiver terminates once all workers are done.
+        drop(sender);
 
-        let mut output = vec![];
+        let mut pending: Vec<Option<Vec<PartReport>>> = vec![None; puzzles.len()];
+        let mut printed = 0;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        for (index, output) in receiver {
+            pending[index] = Some(output);
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            while let Some(output) = pending.get_mut(printed).and_then(Option::take) {
+                on_done(puzzles[printed], output);
+                printed += 1;
+            }
         }
+    });
+}
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
-    }
+fn collect_timings(output: &[PartReport], puzzle: PuzzleId) -> Timings {
+    let mut timings = Timings {
+        puzzle,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+    };
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    output
+        .iter()
+        .filter(|report| report.is_benchmarked())
+        .for_each(|report| {
+            match report.phase {
+                Phase::Parse => timings.parse = report.stats,
+                Phase::Part(Part::ONE) => timings.part_1 = report.stats,
+                Phase::Part(Part::TWO) => timings.part_2 = report.stats,
+                Phase::Part(_) => {}
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += report.duration.as_nanos() as f64;
+            }
+        });
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    timings
+}
 
-                timings.total_nanos += nanos;
-            });
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
 
-        timings
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{collect_timings, PartReport, Phase};
+
+    use crate::template::report::NO_ANSWER;
+    use crate::template::stats::Stats;
+    use crate::{day, year, Part, PuzzleId};
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(1));
+
+    fn report(phase: Phase, answer: Option<&str>, nanos: u64, samples: u128) -> PartReport {
+        let duration = Duration::from_nanos(nanos);
+        PartReport {
+            puzzle: PUZZLE,
+            phase,
+            answer: answer.map(Into::into),
+            duration,
+            samples,
+            stats: (samples > 1)
+                .then(|| Stats::from_samples(&[duration]))
+                .flatten(),
+            alloc: None,
+            expected: None,
+            error: answer.is_none().then(|| NO_ANSWER.into()),
+            variants: vec![],
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    #[test]
+    fn test_well_formed() {
+        let res = collect_timings(
+            &[
+                report(Phase::Part(Part::ONE), Some("0"), 74, 100_000),
+                report(Phase::Part(Part::TWO), Some("10"), 74_130_000, 99_999),
+            ],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap().mean, Duration::from_nanos(74));
+        assert_eq!(res.part_2.unwrap().mean, Duration::from_nanos(74_130_000));
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn test_unbenchmarked_parts() {
+        let res = collect_timings(
+            &[
+                report(Phase::Part(Part::ONE), Some("0"), 2_000_000_000, 5),
+                report(Phase::Part(Part::TWO), Some("10s"), 100_000_000, 1),
+            ],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 2000000000_f64);
+        assert_eq!(res.part_1.unwrap().mean, Duration::from_secs(2));
+        assert!(res.part_2.is_none());
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn test_parse_phase() {
+        let res = collect_timings(
+            &[
+                report(Phase::Parse, None, 500, 10),
+                report(Phase::Part(Part::ONE), Some("0"), 1_000, 10),
+                report(Phase::Part(Part::TWO), Some("1"), 2_000, 10),
+            ],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 3500_f64);
+        assert_eq!(res.parse.unwrap().mean, Duration::from_nanos(500));
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn test_missing_parts() {
+        let res = collect_timings(
+            &[
+                report(Phase::Part(Part::ONE), None, 0, 1),
+                report(Phase::Part(Part::TWO), None, 0, 1),
+            ],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert!(res.part_1.is_none());
+        assert!(res.part_2.is_none());
     }
 }
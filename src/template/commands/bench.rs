@@ -0,0 +1,85 @@
+use std::process;
+
+use crate::template::bench_history::{self, BenchmarkRun};
+use crate::template::datetime::DateTime;
+use crate::template::report::PartReport;
+use crate::template::worker;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::Year;
+
+/// The default of `--threshold`, in percent.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Stores the benchmarked parts of a run in the history of its year.
+/// Nothing is stored if a part timed out, its abandoned thread slowed down the parts that ran after it.
+pub fn record(year: Year, reports: &[PartReport]) {
+    if worker::has_abandoned() {
+        eprintln!("Skipped recording benchmarks in history, a part that timed out kept running and skewed the timings.");
+        return;
+    }
+
+    if let Err(e) = bench_history::record(year, &BenchmarkRun::new(reports)) {
+        eprintln!("Failed to record benchmarks in history: {e}");
+    }
+}
+
+/// Compares the latest benchmark of every part of a year against the benchmark before it.
+/// Exits with a non-zero status if any part got slower by more than `threshold` percent.
+pub fn handle_compare(year: Year, threshold: f64) {
+    let runs = bench_history::load(year);
+
+    let Some(latest) = runs.last() else {
+        eprintln!(
+            "No benchmarks recorded in \"{}\". Type `cargo time --year {year}` to record some.",
+            bench_history::get_path(year).display()
+        );
+        process::exit(1);
+    };
+
+    println!(
+        "{} run{} recorded, the latest on {} ({}).",
+        runs.len(),
+        if runs.len() == 1 { "" } else { "s" },
+        DateTime::from_timestamp(latest.timestamp),
+        latest.short_commit()
+    );
+
+    let comparisons = bench_history::compare(&runs);
+    let mut regressions = 0;
+
+    for comparison in &comparisons {
+        let is_regression = comparison.is_regression(threshold);
+        if is_regression {
+            regressions += 1;
+        }
+
+        println!(
+            "{ANSI_BOLD}Day {}{ANSI_RESET} {}: {:.1?} → {:.1?} ({:+.1}%, {} → {}){}",
+            comparison.puzzle.day,
+            comparison.phase,
+            comparison.baseline,
+            comparison.latest,
+            comparison.change(),
+            comparison.baseline_commit,
+            comparison.latest_commit,
+            if is_regression { " ✘" } else { "" }
+        );
+    }
+
+    println!("---");
+
+    if comparisons.is_empty() {
+        println!("🎄 No part was benchmarked more than once.");
+    } else if regressions == 0 {
+        println!(
+            "🎄 No part got slower by more than {threshold}% ({} compared).",
+            comparisons.len()
+        );
+    } else {
+        println!(
+            "🎄 {regressions} of {} parts got slower by more than {threshold}%.",
+            comparisons.len()
+        );
+        process::exit(1);
+    }
+}
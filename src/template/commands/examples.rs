@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    process,
+};
+
+use crate::template::{get_data_path, puzzle};
+use crate::PuzzleId;
+
+/// Number of lines shown per code block when asking which blocks to save.
+const PREVIEW_LINES: usize = 4;
+
+/// Extracts example inputs from the puzzle description of a day.
+/// The first selected block is written to `data/<year>/examples/NN.txt`, the k-th one to `data/<year>/examples/NN-k.txt`.
+/// `indices` are 1-based positions of code blocks; without them, the user is asked if there is more than one block.
+pub fn handle(puzzle: PuzzleId, indices: Option<Vec<usize>>, overwrite: bool) {
+    let Some(markdown) = puzzle::load(puzzle) else {
+        eprintln!("{}", puzzle::missing_message(puzzle));
+        process::exit(1);
+    };
+
+    let blocks = puzzle::example_blocks(&markdown);
+
+    if blocks.is_empty() {
+        eprintln!("The puzzle description of {puzzle} does not contain any code blocks.");
+        process::exit(1);
+    }
+
+    let indices = match indices {
+        Some(indices) => indices,
+        None if blocks.len() == 1 => vec![1],
+        None => select(&blocks),
+    };
+
+    for (position, index) in indices.iter().enumerate() {
+        let Some(block) = index.checked_sub(1).and_then(|i| blocks.get(i)) else {
+            eprintln!(
+                "There is no code block {index}, expecting a number from 1 to {}.",
+                blocks.len()
+            );
+            process::exit(1);
+        };
+
+        let file_name = if position == 0 {
+            format!("{}.txt", puzzle.day)
+        } else {
+            format!("{}-{}.txt", puzzle.day, position + 1)
+        };
+        let path = get_data_path(puzzle.year, "examples", &file_name);
+
+        let has_content = fs::read_to_string(&path).is_ok_and(|s| !s.trim().is_empty());
+
+        if has_content && !overwrite {
+            println!(
+                "Skipped \"{}\", it is not empty. Append `--overwrite` to replace it.",
+                path.display()
+            );
+            continue;
+        }
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, format!("{}\n", block.trim_end())));
+
+        match result {
+            Ok(()) => println!("Wrote code block {index} to \"{}\"", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Lists all code blocks and asks which ones to save.
+fn select(blocks: &[String]) -> Vec<usize> {
+    if !io::stdin().is_terminal() {
+        println!(
+            "Found {} code blocks, saving the first one. Use `--index` to choose others.",
+            blocks.len()
+        );
+        return vec![1];
+    }
+
+    println!("Found {} code blocks:", blocks.len());
+
+    for (i, block) in blocks.iter().enumerate() {
+        let line_count = block.lines().count();
+        println!("\n[{}] {line_count} line(s)", i + 1);
+        for line in block.lines().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if line_count > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+
+    loop {
+        print!("\nSelect the blocks to save, separated by commas (default: 1): ");
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+            process::exit(1);
+        }
+
+        if line.trim().is_empty() {
+            return vec![1];
+        }
+
+        match parse_indices(line.trim()) {
+            Ok(indices) if indices.iter().all(|&i| (1..=blocks.len()).contains(&i)) => {
+                return indices;
+            }
+            _ => println!("Expecting numbers from 1 to {}.", blocks.len()),
+        }
+    }
+}
+
+/// Parses a comma-separated list of block indices, e.g. `1,3`.
+pub fn parse_indices(s: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+    s.split(',').map(|x| x.trim().parse()).collect()
+}
@@ -0,0 +1,307 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::input::Input;
+use crate::template::report::{PartReport, Phase};
+use crate::template::runner::RunOptions;
+use crate::template::{answers, get_data_path, solution, Solution, ANSI_BOLD, ANSI_RESET};
+use crate::{Part, PuzzleId};
+
+/// The result of a part for one input, as shown in a cell of the matrix.
+#[derive(Debug, PartialEq)]
+enum Cell {
+    Pass(String),
+    Fail {
+        answer: String,
+        expected: String,
+    },
+    /// There is no answer file to compare against.
+    Unknown(String),
+    /// The solution did not produce an answer.
+    NoAnswer {
+        expected: Option<String>,
+    },
+    /// The part failed, e.g. because it panicked, timed out or its variants disagree.
+    Error {
+        answer: Option<String>,
+        reason: String,
+    },
+}
+
+impl Cell {
+    fn new(report: &PartReport) -> Self {
+        if let Some(reason) = report.failure() {
+            return Cell::Error {
+                answer: report.answer.clone(),
+                reason: reason.into(),
+            };
+        }
+
+        let Some(answer) = report.answer.clone() else {
+            return Cell::NoAnswer {
+                expected: report.expected.clone(),
+            };
+        };
+
+        match (report.is_correct(), report.expected.clone()) {
+            (Some(true), _) => Cell::Pass(answer),
+            (Some(false), Some(expected)) => Cell::Fail { answer, expected },
+            _ => Cell::Unknown(answer),
+        }
+    }
+
+    fn render(&self) -> String {
+        // multi-line answers would break the table.
+        let short = |answer: &str| {
+            if answer.contains('\n') {
+                "▼".to_string()
+            } else {
+                answer.to_string()
+            }
+        };
+
+        match self {
+            Cell::Pass(answer) => format!("✔ {}", short(answer)),
+            Cell::Fail { answer, expected } => {
+                format!("✘ {} (expected {})", short(answer), short(expected))
+            }
+            Cell::Unknown(answer) => format!("? {}", short(answer)),
+            Cell::NoAnswer { expected: None } => "✖".into(),
+            Cell::NoAnswer {
+                expected: Some(expected),
+            } => format!("✖ (expected {})", short(expected)),
+            Cell::Error { answer: None, .. } => "✖".into(),
+            Cell::Error {
+                answer: Some(answer),
+                ..
+            } => format!("✖ {}", short(answer)),
+        }
+    }
+}
+
+/// Runs a solution against every input in `data/<year>/inputs/<day>/` and prints a matrix of results.
+/// Results are compared against the answers in `data/<year>/answers/<day>/<name>-<part>.txt`.
+/// Exits with a non-zero status if any part does not produce its answer.
+pub fn handle(solutions: &[&'static dyn Solution], puzzle: PuzzleId, options: &RunOptions) {
+    let Some(solution) = solution::find(solutions, puzzle) else {
+        eprintln!("{}", solution::missing_message(puzzle));
+        process::exit(1);
+    };
+
+    let dir = get_data_path(puzzle.year, "inputs", &puzzle.day.to_string());
+    let inputs = find_inputs(&dir);
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs found in \"{}\". Add them as `<name>.txt` files, and their answers as `data/{}/answers/{}/<name>-<part>.txt`.",
+            dir.display(),
+            puzzle.year,
+            puzzle.day
+        );
+        process::exit(1);
+    }
+
+    let parts: Vec<Part> = Part::both()
+        .into_iter()
+        .filter(|&part| options.runs(puzzle, part))
+        .collect();
+
+    let rows: Vec<(String, Vec<Cell>)> = inputs
+        .into_iter()
+        .map(|(name, path)| {
+            let options = RunOptions {
+                input: Input::File(path),
+                quiet: true,
+                verify: false,
+                submit: None,
+                ..options.clone()
+            };
+
+            let reports = solution::run(solution, &options);
+
+            let cells = parts
+                .iter()
+                .map(|&part| {
+                    let report = reports
+                        .iter()
+                        .find(|report| report.phase == Phase::Part(part));
+                    let expected = answers::load_for_input(puzzle, &name, part);
+
+                    match report {
+                        Some(report) => Cell::new(&PartReport {
+                            expected,
+                            ..report.clone()
+                        }),
+                        // the solution panicked before reporting this part.
+                        None => Cell::NoAnswer { expected },
+                    }
+                })
+                .collect();
+
+            (name, cells)
+        })
+        .collect();
+
+    print_matrix(&parts, &rows);
+
+    let count = |f: fn(&Cell) -> bool| {
+        rows.iter()
+            .flat_map(|(_, cells)| cells)
+            .filter(|c| f(c))
+            .count()
+    };
+    let passed = count(|cell| matches!(cell, Cell::Pass(_)));
+    let failed = count(|cell| {
+        matches!(
+            cell,
+            Cell::Fail { .. } | Cell::NoAnswer { expected: Some(_) } | Cell::Error { .. }
+        )
+    });
+    let unknown =
+        count(|cell| matches!(cell, Cell::Unknown(_) | Cell::NoAnswer { expected: None }));
+
+    println!("---");
+    println!("🎄 {passed} passed, {failed} failed, {unknown} without answer file.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Lists the `.txt` files of a directory as `(name, path)`, ordered by name.
+fn find_inputs(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut inputs: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let is_txt = path.extension().is_some_and(|ext| ext == "txt");
+            let name = path.file_stem()?.to_str()?.to_string();
+            (is_txt && path.is_file()).then_some((name, path))
+        })
+        .collect();
+
+    inputs.sort();
+    inputs
+}
+
+/// Prints the results as a table. Failed parts refer to a numbered note below the table that explains the failure.
+fn print_matrix(parts: &[Part], rows: &[(String, Vec<Cell>)]) {
+    let header: Vec<String> = std::iter::once("Input".to_string())
+        .chain(parts.iter().map(|part| format!("Part {part}")))
+        .collect();
+
+    let mut notes: Vec<String> = vec![];
+    let mut lines: Vec<Vec<String>> = vec![];
+
+    for (name, cells) in rows {
+        let mut line = vec![name.clone()];
+
+        for (cell, part) in cells.iter().zip(parts) {
+            let mut rendered = cell.render();
+            if let Cell::Error { reason, .. } = cell {
+                notes.push(format!("{name}, part {part}: {reason}"));
+                rendered.push_str(&format!(" [{}]", notes.len()));
+            }
+            line.push(rendered);
+        }
+
+        lines.push(line);
+    }
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(&lines)
+                .map(|line| line[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let pad = |line: &[String]| -> String {
+        line.iter()
+            .zip(&widths)
+            .map(|(s, &width)| format!("{s}{}", " ".repeat(width - s.chars().count())))
+            .collect::<Vec<String>>()
+            .join("   ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", pad(&header));
+    for line in &lines {
+        println!("{}", pad(line));
+    }
+
+    if !notes.is_empty() {
+        println!();
+        for (i, note) in notes.iter().enumerate() {
+            println!("[{}] {note}", i + 1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Cell;
+    use crate::template::report::{PartReport, Phase};
+    use crate::{day, year, Part, PuzzleId};
+
+    fn report(answer: Option<&str>, expected: Option<&str>) -> PartReport {
+        PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(8)),
+            phase: Phase::Part(Part::TWO),
+            answer: answer.map(Into::into),
+            duration: Duration::from_nanos(1),
+            samples: 1,
+            stats: None,
+            alloc: None,
+            expected: expected.map(Into::into),
+            error: None,
+            variants: vec![],
+        }
+    }
+
+    #[test]
+    fn compares_answers() {
+        let cell = |answer, expected| Cell::new(&report(answer, expected)).render();
+
+        assert_eq!(cell(Some("6"), Some("6\n")), "✔ 6");
+        assert_eq!(cell(Some("5"), Some("6")), "✘ 5 (expected 6)");
+        assert_eq!(cell(Some("5"), None), "? 5");
+        assert_eq!(cell(None, Some("6")), "✖ (expected 6)");
+        assert_eq!(cell(None, None), "✖");
+    }
+
+    #[test]
+    fn fails_on_errors() {
+        let failed = |answer: Option<&str>, error: &str| {
+            Cell::new(&PartReport {
+                error: Some(error.into()),
+                ..report(answer, Some("6"))
+            })
+        };
+
+        assert_eq!(
+            failed(None, "timeout"),
+            Cell::Error {
+                answer: None,
+                reason: "timeout".into()
+            }
+        );
+        assert_eq!(failed(None, "timeout").render(), "✖");
+
+        // the answer matches, but a variant does not.
+        let disagreement = failed(Some("6"), "variant part_two_alt disagrees");
+        assert_eq!(disagreement.render(), "✖ 6");
+        assert!(matches!(disagreement, Cell::Error { .. }));
+    }
+}
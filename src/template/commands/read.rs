@@ -1,16 +1,13 @@
 use std::process;
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::backend;
+use crate::PuzzleId;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: PuzzleId) {
+    let result = backend::select().and_then(|backend| backend.read(puzzle));
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = result {
+        eprintln!("{e}");
         process::exit(1);
     };
 }
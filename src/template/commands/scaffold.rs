@@ -1,12 +1,14 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{get_data_path, puzzle};
+use crate::PuzzleId;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
 pub fn part_one(input: &str) -> Option<u32> {
     None
@@ -16,36 +18,123 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+"#;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+const TEST_TEMPLATE: &str = r#"#[test]
+    fn test_PART_NAME() {
+        let result = PART_CALL;
+        assert_eq!(result, EXPECTED);
+    }"#;
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// Names of the tests generated for each part.
+const TEST_NAMES: [&str; 2] = ["test_part_one", "test_part_two"];
+
+/// The example answers found in the puzzle description, if it was downloaded.
+fn example_answers(puzzle: PuzzleId) -> [Option<String>; 2] {
+    puzzle::load(puzzle).map_or([None, None], |markdown| puzzle::example_answers(&markdown))
+}
+
+/// Parts of the module template return an `Option<u32>`, which can't hold a negative example answer.
+/// Changes the return type of such parts to `Option<i64>`, as long as they were not implemented yet.
+fn sign_stubs(code: &str, answers: &[Option<String>; 2]) -> String {
+    let stub = |name: &str, ty: &str| {
+        format!("pub fn {name}(input: &str) -> Option<{ty}> {{\n    None\n}}")
+    };
+
+    ["part_one", "part_two"]
+        .iter()
+        .zip(answers)
+        .filter(|(_, answer)| {
+            answer
+                .as_ref()
+                .is_some_and(|answer| answer.starts_with('-'))
+        })
+        .fold(code.to_string(), |code, (name, _)| {
+            code.replace(&stub(name, "u32"), &stub(name, "i64"))
+        })
+}
+
+/// Generates the test module of a solution, asserting the example answers found in the puzzle description.
+/// `parse` is the name of the parse function of a two-phase solution.
+/// `other_items` are appended to the generated tests, e.g. tests that were written by hand.
+fn tests_module(
+    answers: &[Option<String>; 2],
+    puzzle: PuzzleId,
+    parse: Option<&str>,
+    other_items: &str,
+) -> String {
+    let mut tests: Vec<String> = [(1, "part_one"), (2, "part_two")]
+        .iter()
+        .zip(answers)
+        .map(|(&(part, name), answer)| {
+            let has_part_example = get_data_path(
+                puzzle.year,
+                "examples",
+                &format!("{}-{part}.txt", puzzle.day),
+            )
+            .exists();
+
+            let input = if part > 1 && has_part_example {
+                format!("&advent_of_code::template::read_file_part(\"examples\", PUZZLE, {part})")
+            } else {
+                "&advent_of_code::template::read_file(\"examples\", PUZZLE)".to_string()
+            };
+
+            let call = match parse {
+                Some(parse) => format!("{name}(&{parse}({input}))"),
+                None => format!("{name}({input})"),
+            };
+
+            TEST_TEMPLATE
+                .replace("PART_NAME", name)
+                .replace("PART_CALL", &call)
+                .replace(
+                    "EXPECTED",
+                    &answer
+                        .as_ref()
+                        .map_or("None".into(), |x| format!("Some({x})")),
+                )
+        })
+        .collect();
+
+    if !other_items.is_empty() {
+        tests.push(other_items.to_string());
     }
+
+    format!(
+        "#[cfg(test)]\nmod tests {{\n    use super::*;\n\n    {}\n}}\n",
+        tests.join("\n\n    ")
+    )
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn create_parent_dir(path: &Path) -> Result<(), std::io::Error> {
+    path.parent().map_or(Ok(()), fs::create_dir_all)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, refresh_tests: bool) {
+    if refresh_tests {
+        self::refresh_tests(puzzle);
+        return;
+    }
+
+    let day = puzzle.day;
+    let input_path = get_data_path(puzzle.year, "inputs", &format!("{day}.txt"));
+    let example_path = get_data_path(puzzle.year, "examples", &format!("{day}.txt"));
+    let module_path = get_module_path(puzzle);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,13 +144,15 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let answers = example_answers(puzzle);
+    let template = MODULE_TEMPLATE
+        .replace("YEAR_NUMBER", &puzzle.year.into_inner().to_string())
+        .replace("DAY_NUMBER", &day.into_inner().to_string());
+    let module = sign_stubs(&template, &answers) + &tests_module(&answers, puzzle, None, "");
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -71,7 +162,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -79,10 +170,23 @@ pub fn handle(day: Day) {
         }
     }
 
+    let example = puzzle::load(puzzle)
+        .and_then(|markdown| puzzle::example_blocks(&markdown).into_iter().next());
+
     match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+        Ok(mut file) => match example {
+            Some(example) if file.metadata().is_ok_and(|m| m.len() == 0) => {
+                if let Err(e) = writeln!(file, "{}", example.trim_end()) {
+                    eprintln!("Failed to write example file: {e}");
+                    process::exit(1);
+                }
+                println!(
+                    "Created example file \"{}\" from the puzzle description. Type `cargo examples {} --year {}` to pick a different code block.",
+                    example_path.display(), day, puzzle.year
+                );
+            }
+            _ => println!("Created empty example file \"{}\"", example_path.display()),
+        },
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
@@ -90,5 +194,214 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        day, puzzle.year
+    );
+}
+
+fn get_module_path(puzzle: PuzzleId) -> std::path::PathBuf {
+    Path::new("src").join("bin").join(format!("{puzzle}.rs"))
+}
+
+/// Regenerates the tests of an existing solution, e.g. to pick up the example answer of part two once it unlocked.
+/// Other items of its test module and the code after it are kept.
+fn refresh_tests(puzzle: PuzzleId) {
+    let module_path = get_module_path(puzzle);
+
+    if puzzle::load(puzzle).is_none() {
+        eprintln!("{}", puzzle::missing_message(puzzle));
+        process::exit(1);
+    }
+
+    let source = match fs::read_to_string(&module_path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!(
+                "Failed to read module file \"{}\": {e}",
+                module_path.display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let (code, other_items, rest) = split_tests_module(&source);
+    let answers = example_answers(puzzle);
+
+    let mut updated = format!(
+        "{}\n\n{}",
+        sign_stubs(code, &answers).trim_end(),
+        tests_module(&answers, puzzle, find_parse_function(code), &other_items)
+    );
+
+    if !rest.trim().is_empty() {
+        updated.push('\n');
+        updated.push_str(rest.trim_start());
+    }
+
+    match fs::write(&module_path, updated) {
+        Ok(()) if other_items.is_empty() => {
+            println!("Updated tests in module file \"{}\"", module_path.display());
+        }
+        Ok(()) => println!(
+            "Updated tests in module file \"{}\", kept the tests that were not generated.",
+            module_path.display()
+        ),
+        Err(e) => {
+            eprintln!("Failed to write module contents: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Finds the parse function passed to `solution!`, e.g. `parse` in `solution!(2023, 5, parse)`.
+fn find_parse_function(code: &str) -> Option<&str> {
+    let start = code.find("solution!(")? + "solution!(".len();
+    let len = code[start..].find(')')?;
+    let parse = code[start..start + len].split(',').nth(2)?;
+    // the third argument may also be the variants of the parts, e.g. `solution!(2023, 5, variants: { .. })`.
+    Some(parse.trim()).filter(|parse| !parse.is_empty() && !parse.starts_with("variants"))
+}
+
+/// Splits a solution into the code before its test module, the items of the test module that were not
+/// generated by `scaffold` (e.g. additional tests and their helpers) and the code after the module.
+/// Without a test module, all of the code comes before it.
+fn split_tests_module(source: &str) -> (&str, String, &str) {
+    let Some(start) = source.find("#[cfg(test)]\nmod tests {") else {
+        return (source, String::new(), "");
+    };
+
+    let Some(end) = end_of_block(source, start) else {
+        return (&source[..start], String::new(), "");
+    };
+
+    let open = start + source[start..].find('{').unwrap_or(0);
+    let mut body = source[open + 1..end - 1].to_string();
+
+    for name in TEST_NAMES {
+        let Some(fn_start) = body.find(&format!("fn {name}(")) else {
+            continue;
+        };
+        let Some(fn_end) = end_of_block(&body, fn_start) else {
+            continue;
+        };
+        let item_start = body[..fn_start]
+            .trim_end()
+            .strip_suffix("#[test]")
+            .map_or(fn_start, str::len);
+        body.replace_range(item_start..fn_end, "");
+    }
+
+    let other_items = body.replacen("use super::*;", "", 1).trim().to_string();
+    (&source[..start], other_items, &source[end..])
+}
+
+/// Returns the index after the brace that closes the first block opened at or after `start`.
+/// Braces in comments, string literals and char literals are ignored.
+fn end_of_block(code: &str, start: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut depth = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' if depth == 1 => return Some(i + 1),
+            b'}' => depth -= 1,
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            // `'{'` and `'\u{7b}'`, but not lifetimes such as `'a`.
+            b'\'' if bytes.get(i + 1) == Some(&b'\\') => {
+                i += 3 + code[i + 3..].find('\'')?;
+            }
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += code[i..].find('\n').unwrap_or(code.len() - i);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{sign_stubs, split_tests_module, MODULE_TEMPLATE};
+
+    #[test]
+    fn keeps_tests_that_were_not_generated() {
+        let source = r#"advent_of_code::solution!(2023, 10);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two_enclosed() {
+        // a `}` in a comment, and in literals: "}", '}'
+        assert_eq!(part_two("|"), Some(4));
+    }
+}
+
+fn helper<'a>(s: &'a str) -> &'a str {
+    s
+}
+"#;
+
+        let (code, other_items, rest) = split_tests_module(source);
+
+        assert!(code.ends_with("    None\n}\n\n"));
+        assert_eq!(
+            other_items,
+            r#"#[test]
+    fn test_part_two_enclosed() {
+        // a `}` in a comment, and in literals: "}", '}'
+        assert_eq!(part_two("|"), Some(4));
+    }"#
+        );
+        assert_eq!(
+            rest,
+            "\n\nfn helper<'a>(s: &'a str) -> &'a str {\n    s\n}\n"
+        );
+    }
+
+    #[test]
+    fn splits_solution_without_tests() {
+        let source = "advent_of_code::solution!(2023, 10);\n";
+        assert_eq!(split_tests_module(source), (source, String::new(), ""));
+    }
+
+    #[test]
+    fn signs_stubs_of_negative_answers() {
+        let module = sign_stubs(MODULE_TEMPLATE, &[Some("114".into()), Some("-2".into())]);
+        assert!(module.contains("part_one(input: &str) -> Option<u32>"));
+        assert!(module.contains("part_two(input: &str) -> Option<i64>"));
+
+        let implemented = "pub fn part_two(input: &str) -> Option<i64> {\n    Some(-2)\n}\n";
+        assert_eq!(
+            sign_stubs(implemented, &[None, Some("-2".into())]),
+            implemented
+        );
+    }
 }
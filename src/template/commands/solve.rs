@@ -1,31 +1,28 @@
-use std::process::{Command, Stdio};
-
-use crate::Day;
-
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if release {
-        cmd_args.push("--release".to_string());
+use std::process;
+
+use crate::template::commands::bench;
+use crate::template::runner::{self, RunOptions};
+use crate::template::{solution, Solution};
+use crate::PuzzleId;
+
+pub fn handle(
+    solutions: &[&'static dyn Solution],
+    puzzle: PuzzleId,
+    is_release: bool,
+    options: &RunOptions,
+) {
+    let Some(solution) = solution::find(solutions, puzzle) else {
+        eprintln!("{}", solution::missing_message(puzzle));
+        process::exit(1);
+    };
+
+    let reports = runner::run_solution(solution, options);
+    runner::finish(&reports, options);
+
+    // benchmarks of examples or other inputs can't be compared with those of the puzzle input.
+    if options.time && is_release && options.input.is_puzzle() {
+        bench::record(puzzle.year, &reports);
     }
 
-    cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
-
-    if time {
-        cmd_args.push("--time".to_string());
-    }
-
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
-
-    cmd.wait().unwrap();
+    runner::exit_on_failure(&reports);
 }
@@ -0,0 +1,80 @@
+use std::process;
+
+use crate::template::report::{PartReport, Phase, NO_ANSWER};
+use crate::template::runner::RunOptions;
+use crate::template::{answers, solution, Solution, ANSI_BOLD, ANSI_RESET};
+use crate::{DaySet, Part, PuzzleId, Year};
+
+/// Runs every solved day of `days` that has accepted answers and compares the results.
+/// Exits with a non-zero status if any part does not produce its accepted answer.
+pub fn handle(solutions: &[&'static dyn Solution], year: Year, days: DaySet) {
+    let options = RunOptions {
+        quiet: true,
+        verify: true,
+        ..RunOptions::default()
+    };
+
+    let mut correct = 0;
+    let mut wrong = 0;
+
+    for day in days.iter() {
+        let puzzle = PuzzleId::new(year, day);
+
+        let Some(solution) = solution::find(solutions, puzzle) else {
+            continue;
+        };
+
+        if !has_answers(puzzle) {
+            continue;
+        }
+
+        let reports = solution::run(solution, &options);
+
+        let results: Vec<String> = Part::both()
+            .into_iter()
+            .filter(|part| part.exists_on(day))
+            .filter_map(|part| {
+                let expected = answers::load(puzzle, part)?;
+                let report = reports
+                    .iter()
+                    .find(|report| report.phase == Phase::Part(part));
+
+                let failure = report.and_then(PartReport::failure);
+
+                if failure.is_none() && report.and_then(PartReport::is_correct) == Some(true) {
+                    correct += 1;
+                    Some(format!("Part {part} ✔"))
+                } else {
+                    wrong += 1;
+                    let answer = report
+                        .and_then(|report| report.answer.clone())
+                        .unwrap_or_else(|| NO_ANSWER.into());
+                    Some(match failure {
+                        Some(failure) => format!("Part {part} ✘ (expected {expected}, {failure})"),
+                        None => format!("Part {part} ✘ (expected {expected}, got {answer})"),
+                    })
+                }
+            })
+            .collect();
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}: {}", results.join(" · "));
+    }
+
+    println!("---");
+
+    if correct + wrong == 0 {
+        println!("🎄 No accepted answers found in \"data/{year}/answers\".");
+    } else if wrong == 0 {
+        println!("🎄 All {correct} answers are correct.");
+    } else {
+        println!("🎄 {correct} correct, {wrong} wrong.");
+        process::exit(1);
+    }
+}
+
+fn has_answers(puzzle: PuzzleId) -> bool {
+    Part::both()
+        .into_iter()
+        .filter(|part| part.exists_on(puzzle.day))
+        .any(|part| answers::load(puzzle, part).is_some())
+}
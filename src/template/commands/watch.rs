@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::{get_data_path, solution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::PuzzleId;
+
+/// How often the watched files are checked for modifications.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Re-runs the tests of a day, or its solution if `solve` is set, whenever one of its files is modified.
+/// Watches the solution, `src/common.rs` and the examples of the day. Runs until interrupted.
+pub fn handle(puzzle: PuzzleId, solve: bool, is_release: bool) {
+    if !Path::new(&get_path_for_bin(puzzle)).is_file() {
+        eprintln!("{}", solution::missing_message(puzzle));
+        process::exit(1);
+    }
+
+    let mut last_snapshot = None;
+
+    loop {
+        let snapshot = snapshot(puzzle);
+
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            print!("{ANSI_CLEAR_SCREEN}");
+            println!(
+                "{ANSI_ITALIC}Watching day {} of {} for changes, press Ctrl-C to stop.{ANSI_RESET}\n",
+                puzzle.day, puzzle.year
+            );
+
+            if solve {
+                run_solution(puzzle, is_release);
+            } else {
+                run_tests(puzzle, is_release);
+            }
+
+            last_snapshot = Some(snapshot);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The watched files of a day and their modification times. Files that don't exist have no modification time.
+fn snapshot(puzzle: PuzzleId) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(puzzle)),
+        PathBuf::from("./src/common.rs"),
+    ];
+
+    // e.g. `01.txt` and `01-2.txt`.
+    let day = puzzle.day.to_string();
+    if let Ok(entries) = fs::read_dir(get_data_path(puzzle.year, "examples", "")) {
+        paths.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let is_example = name.strip_prefix(&day)?.starts_with(['.', '-']);
+            is_example.then_some(path)
+        }));
+    }
+
+    let mut snapshot: Vec<(PathBuf, Option<SystemTime>)> = paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect();
+
+    snapshot.sort();
+    snapshot
+}
+
+fn cargo(subcommand: &str, args: &[String], is_release: bool) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args([subcommand, "--quiet"]);
+
+    if is_release {
+        cmd.arg("--release");
+    }
+
+    cmd.args(args).stdin(Stdio::null());
+    cmd
+}
+
+/// Runs the tests of the solution, prints the failed tests and a summary.
+fn run_tests(puzzle: PuzzleId, is_release: bool) {
+    let args = ["--bin".to_string(), puzzle.to_string()];
+    let timer = Instant::now();
+
+    let output = match cargo("test", &args, is_release).output() {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    };
+
+    let elapsed = timer.elapsed();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    match summarize_tests(&stdout) {
+        Some(summary) if summary.failed == 0 => {
+            println!(
+                "{ANSI_BOLD}✔ {} test{} passed{ANSI_RESET} ({elapsed:.1?})",
+                summary.passed,
+                if summary.passed == 1 { "" } else { "s" }
+            );
+        }
+        Some(summary) => {
+            // the first failures section contains the panic message and the compared values of each failed test,
+            // the second one only lists their names again.
+            if let Some(start) = stdout.find("failures:") {
+                let details = &stdout[start..];
+                let end = details[1..]
+                    .find("\nfailures:")
+                    .or_else(|| details.find("test result:"))
+                    .unwrap_or(details.len());
+                println!("{}", details[..end].trim_end());
+                println!();
+            }
+
+            println!(
+                "{ANSI_BOLD}✘ {} of {} tests failed{ANSI_RESET} ({elapsed:.1?})",
+                summary.failed,
+                summary.passed + summary.failed
+            );
+        }
+        // the tests did not compile, cargo prints the errors to stderr.
+        None => {
+            println!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+            println!();
+            println!("{ANSI_BOLD}✘ build failed{ANSI_RESET}");
+        }
+    }
+}
+
+/// Runs the solution against the puzzle input and prints whether it finished successfully.
+fn run_solution(puzzle: PuzzleId, is_release: bool) {
+    let mut args = vec![
+        "--".to_string(),
+        "solve".into(),
+        puzzle.day.to_string(),
+        "--year".into(),
+        puzzle.year.to_string(),
+    ];
+
+    if is_release {
+        args.push("--release".into());
+    }
+
+    let timer = Instant::now();
+
+    let status = match cargo("run", &args, is_release).status() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    };
+
+    let elapsed = timer.elapsed();
+    println!();
+
+    if status.success() {
+        println!("{ANSI_BOLD}✔ finished{ANSI_RESET} ({elapsed:.1?})");
+    } else {
+        println!("{ANSI_BOLD}✘ failed ({status}){ANSI_RESET} ({elapsed:.1?})");
+    }
+}
+
+/// The number of passed and failed tests of a test run.
+#[derive(Debug, PartialEq, Eq)]
+struct TestSummary {
+    passed: usize,
+    failed: usize,
+}
+
+/// Sums up the `test result:` lines printed by the test harness, returns [`None`] if there are none.
+fn summarize_tests(output: &str) -> Option<TestSummary> {
+    let results: Vec<&str> = output
+        .lines()
+        .filter_map(|line| line.strip_prefix("test result: "))
+        .collect();
+
+    if results.is_empty() {
+        return None;
+    }
+
+    let count = |result: &str, label: &str| -> usize {
+        result
+            .split(['.', ';'])
+            .filter_map(|field| field.trim().strip_suffix(label))
+            .filter_map(|n| n.trim().parse::<usize>().ok())
+            .sum()
+    };
+
+    Some(TestSummary {
+        passed: results.iter().map(|r| count(r, "passed")).sum(),
+        failed: results.iter().map(|r| count(r, "failed")).sum(),
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{summarize_tests, TestSummary};
+
+    #[test]
+    fn summarizes_tests() {
+        let output = "
+running 2 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+
+failures:
+
+---- tests::test_part_two stdout ----
+assertion `left == right` failed
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+        assert_eq!(
+            summarize_tests(output),
+            Some(TestSummary {
+                passed: 1,
+                failed: 1
+            })
+        );
+
+        assert_eq!(
+            summarize_tests("test result: ok. 2 passed; 0 failed; 0 ignored; finished in 0.01s"),
+            Some(TestSummary {
+                passed: 2,
+                failed: 0
+            })
+        );
+
+        assert_eq!(summarize_tests("error[E0308]: mismatched types"), None);
+    }
+}
@@ -0,0 +1,99 @@
+/// Unix timestamps and their calendar date in UTC, used for the cooldown, the benchmark history and the current event.
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the unix epoch, `0` if the system clock is set before it.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// A date and time in UTC, with minute precision.
+///
+/// # Display
+/// This value displays as `2023-12-05 06:00 UTC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: u16,
+    /// From 1 (January) to 12 (December).
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl DateTime {
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from_timestamp(now())
+    }
+
+    /// Converts a unix timestamp to its date and time in UTC.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_timestamp(timestamp: u64) -> Self {
+        let days = timestamp / 86_400;
+        let seconds = timestamp % 86_400;
+
+        // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z % 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+
+        Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds % 3600 / 60) as u8,
+        }
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{:02}-{:02} {:02}:{:02} UTC",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DateTime;
+
+    #[test]
+    fn converts_timestamps() {
+        assert_eq!(
+            DateTime::from_timestamp(1_701_752_400),
+            DateTime {
+                year: 2023,
+                month: 12,
+                day: 5,
+                hour: 5,
+                minute: 0
+            }
+        );
+
+        assert_eq!(
+            DateTime::from_timestamp(0).to_string(),
+            "1970-01-01 00:00 UTC"
+        );
+        assert_eq!(
+            DateTime::from_timestamp(1_709_164_800).to_string(),
+            "2024-02-29 00:00 UTC"
+        );
+    }
+}
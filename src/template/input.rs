@@ -0,0 +1,141 @@
+/// Selects the input a solution is run against.
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{convert::Infallible, fs};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Input {
+    /// The puzzle input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    /// An example in `data/<year>/examples`, `Some(k)` selects the file with suffix `-k`.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// Whether this is the puzzle input, i.e. the one accepted answers and submissions refer to.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == Input::Puzzle
+    }
+
+    /// Reads the input of a puzzle.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, InputError> {
+        let day = puzzle.day;
+
+        match self {
+            Input::Puzzle => read_data_file(puzzle, "inputs", &format!("{day}.txt")),
+            Input::Example(None) => read_data_file(puzzle, "examples", &format!("{day}.txt")),
+            Input::Example(Some(k)) => {
+                read_data_file(puzzle, "examples", &format!("{day}-{k}.txt"))
+            }
+            Input::File(path) => fs::read_to_string(path).map_err(|source| InputError {
+                path: Some(path.clone()),
+                download: None,
+                source,
+            }),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| InputError {
+                        path: None,
+                        download: None,
+                        source,
+                    })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Reads a file in the data directory of a puzzle's year, e.g. `data/2023/inputs/01.txt`.
+pub fn read_data_file(
+    puzzle: PuzzleId,
+    folder: &str,
+    file_name: &str,
+) -> Result<String, InputError> {
+    let path = get_data_path(puzzle.year, folder, file_name);
+
+    fs::read_to_string(&path).map_err(|source| InputError {
+        path: Some(path),
+        // only puzzle inputs can be downloaded.
+        download: (folder == "inputs").then_some(puzzle),
+        source,
+    })
+}
+
+/// An error which can be returned when reading an input.
+#[derive(Debug)]
+pub struct InputError {
+    /// The path that was attempted, [`None`] for stdin.
+    pub path: Option<PathBuf>,
+    /// The puzzle whose input is missing, if it can be downloaded.
+    pub download: Option<PuzzleId>,
+    pub source: io::Error,
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "could not read \"{}\": {}", path.display(), self.source)?,
+            None => write!(f, "could not read stdin: {}", self.source)?,
+        }
+
+        match self.download {
+            Some(puzzle) if self.source.kind() == io::ErrorKind::NotFound => write!(
+                f,
+                ". Type `cargo download {} --year {}` to download it.",
+                puzzle.day, puzzle.year
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses the value of `--input`: `-` reads from stdin, anything else is a path.
+impl FromStr for Input {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => Input::Stdin,
+            path => Input::File(path.into()),
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_data_file, Input};
+    use crate::{day, year, PuzzleId};
+
+    #[test]
+    fn reports_missing_input() {
+        let puzzle = PuzzleId::new(year!(2015), day!(25));
+        let message = Input::Puzzle.read(puzzle).unwrap_err().to_string();
+
+        assert!(message.starts_with("could not read \""));
+        assert!(message.contains("data/2015/inputs/25.txt"));
+        assert!(message.ends_with("Type `cargo download 25 --year 2015` to download it."));
+
+        let message = read_data_file(puzzle, "examples", "25.txt")
+            .unwrap_err()
+            .to_string();
+        assert!(!message.contains("cargo download"));
+    }
+}
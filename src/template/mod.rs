@@ -1,48 +1,164 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{PuzzleId, Year};
+use std::{env, path::PathBuf};
 
+pub mod allocations;
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod backend;
+pub mod bench_history;
 pub mod commands;
+pub mod datetime;
+pub mod input;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod report;
 pub mod runner;
+pub mod solution;
+pub mod stats;
+pub mod submissions;
+pub mod worker;
+
+pub use solution::Solution;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Builds the path of a file in the data directory of a year, e.g. `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(year: Year, folder: &str, file_name: &str) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join(year.to_string())
+        .join(folder)
+        .join(file_name)
+}
+
 /// Helper function that reads a text file to a string.
+/// Meant for tests, panics with the attempted path if the file can't be read.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    input::read_data_file(puzzle, folder, &format!("{}.txt", puzzle.day))
+        .unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    input::read_data_file(puzzle, folder, &format!("{}-{part}.txt", puzzle.day))
+        .unwrap_or_else(|e| panic!("{e}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE`, registers the solution and sets up the input and runner for each part.
+///
+/// Solutions can optionally be split into two phases by passing a parse function as third argument,
+/// e.g. `solution!(2023, 5, parse)`. The input is then parsed once and both parts receive a reference to the parsed value.
+///
+/// Alternative implementations of a part can be registered as variants, e.g. `solution!(2023, 5, parse, variants: { part_two: [part_two_brute_force] })`.
+/// Variants take the same input and return the same type as the part. They run after the part, and the part fails if they don't agree.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@impl $year:expr, $day:expr, |$input:ident, $options:ident| $run:block) => {
+        /// The year of the current puzzle.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        /// The current puzzle, used to locate its data files.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(YEAR, DAY);
+
+        /// The solution of the current puzzle, used to run it in-process.
+        pub struct DaySolution;
+
+        impl advent_of_code::template::Solution for DaySolution {
+            fn puzzle(&self) -> advent_of_code::PuzzleId {
+                PUZZLE
+            }
+
+            fn run(
+                &self,
+                $input: &str,
+                $options: &advent_of_code::template::runner::RunOptions,
+            ) -> Vec<advent_of_code::template::report::PartReport> {
+                $run
+            }
+        }
 
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            advent_of_code::template::runner::run_main(&DaySolution);
         }
     };
+    (@variants part_one; part_one: [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        [$((stringify!($variant), $variant as fn(_) -> _)),*]
+    };
+    (@variants part_two; part_two: [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        [$((stringify!($variant), $variant as fn(_) -> _)),*]
+    };
+    (@variants $part:ident; $other:ident: [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        advent_of_code::solution!(@variants $part; $($($rest)*)?)
+    };
+    (@variants $part:ident;) => {
+        []
+    };
+    ($year:literal, $day:literal, variants: { $($variants:tt)* }) => {
+        advent_of_code::solution!(@impl $year, $day, |input, options| {
+            use advent_of_code::template::runner::*;
+            [
+                run_part_variants(
+                    ("part_one", part_one),
+                    &advent_of_code::solution!(@variants part_one; $($variants)*),
+                    input,
+                    PUZZLE,
+                    advent_of_code::Part::ONE,
+                    options,
+                ),
+                run_part_variants(
+                    ("part_two", part_two),
+                    &advent_of_code::solution!(@variants part_two; $($variants)*),
+                    input,
+                    PUZZLE,
+                    advent_of_code::Part::TWO,
+                    options,
+                ),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        });
+    };
+    ($year:literal, $day:literal, $parse:expr, variants: { $($variants:tt)* }) => {
+        advent_of_code::solution!(@impl $year, $day, |input, options| {
+            use advent_of_code::template::runner::*;
+            let (parsed, parse_report) = run_parse($parse, input, PUZZLE, options);
+            [
+                Some(parse_report),
+                run_part_variants(
+                    ("part_one", part_one),
+                    &advent_of_code::solution!(@variants part_one; $($variants)*),
+                    &parsed,
+                    PUZZLE,
+                    advent_of_code::Part::ONE,
+                    options,
+                ),
+                run_part_variants(
+                    ("part_two", part_two),
+                    &advent_of_code::solution!(@variants part_two; $($variants)*),
+                    &parsed,
+                    PUZZLE,
+                    advent_of_code::Part::TWO,
+                    options,
+                ),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        });
+    };
+    ($year:literal, $day:literal, $parse:expr) => {
+        advent_of_code::solution!($year, $day, $parse, variants: {});
+    };
+    ($year:literal, $day:literal) => {
+        advent_of_code::solution!($year, $day, variants: {});
+    };
 }
@@ -0,0 +1,132 @@
+/// Helpers to extract information from puzzle descriptions downloaded to `data/<year>/puzzles`.
+use std::{fs, path::PathBuf};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    get_data_path(puzzle.year, "puzzles", &format!("{}.md", puzzle.day))
+}
+
+/// Reads the description of a puzzle, returns [`None`] if it was not downloaded yet.
+#[must_use]
+pub fn load(puzzle: PuzzleId) -> Option<String> {
+    fs::read_to_string(get_path(puzzle)).ok()
+}
+
+/// The error shown by commands that need the description of a puzzle that was not downloaded yet.
+#[must_use]
+pub fn missing_message(puzzle: PuzzleId) -> String {
+    format!(
+        "No puzzle description found at \"{}\". Type `cargo download {} --year {}` to download it.",
+        get_path(puzzle).display(),
+        puzzle.day,
+        puzzle.year
+    )
+}
+
+/// Returns the contents of all fenced code blocks that could be example inputs, in order of appearance.
+/// Empty blocks and repetitions of an earlier block are skipped.
+#[must_use]
+pub fn example_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = vec![];
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(lines) => {
+                    let block = lines.join("\n");
+                    if !block.trim().is_empty() && !blocks.contains(&block) {
+                        blocks.push(block);
+                    }
+                }
+                None => current = Some(vec![]),
+            }
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+
+    blocks
+}
+
+/// Detects the answers to the examples of both parts.
+/// The answer of a part is the last emphasized number in the last paragraph of that part which contains one.
+#[must_use]
+pub fn example_answers(markdown: &str) -> [Option<String>; 2] {
+    let (part_one, part_two) = match markdown.find("--- Part Two ---") {
+        Some(i) => (&markdown[..i], Some(&markdown[i..])),
+        None => (markdown, None),
+    };
+
+    [
+        last_emphasized_number(part_one),
+        part_two.and_then(last_emphasized_number),
+    ]
+}
+
+fn last_emphasized_number(section: &str) -> Option<String> {
+    let mut paragraphs = vec![];
+    let mut in_code_block = false;
+    let mut paragraph = String::new();
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if in_code_block {
+            continue;
+        } else if line.trim().is_empty() {
+            paragraphs.push(std::mem::take(&mut paragraph));
+        } else {
+            paragraph.push_str(line);
+            paragraph.push('\n');
+        }
+    }
+
+    paragraphs.push(paragraph);
+
+    paragraphs.iter().rev().find_map(|paragraph| {
+        paragraph
+            .replace("**", "*")
+            .split('*')
+            .skip(1)
+            .step_by(2)
+            .map(|emphasized| emphasized.trim_matches('`').trim())
+            .filter(|x| x.parse::<i128>().is_ok())
+            .last()
+            .map(str::to_string)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_answers, example_blocks};
+
+    #[test]
+    fn extracts_example_blocks() {
+        let markdown = "## --- Day 1 ---\n\nFor example:\n\n```\n1abc2\npqr3stu8vwx\n```\n\nSome text with `code`.\n\n```\n```\n\n```text\n1abc2\npqr3stu8vwx\n```\n\n```\ntwo1nine\n\n```\n";
+
+        assert_eq!(
+            example_blocks(markdown),
+            vec!["1abc2\npqr3stu8vwx", "two1nine\n"]
+        );
+        assert!(example_blocks("no code blocks").is_empty());
+    }
+
+    #[test]
+    fn detects_example_answers() {
+        let markdown = "## --- Day 1: Trebuchet?! ---\n\nThe *calibration values* of these lines are `*12*` and *38*.\n\n```\n1abc2\n*7*\n```\n\nAdding these together produces *`50`*.\n\nWhat is the *sum*?\n\n## --- Part Two ---\n\nIn this example, the result is **-281**, not *2*.\n\n- a list with *4*\n\nWhat is the sum?\n";
+
+        assert_eq!(
+            example_answers(markdown),
+            [Some("50".into()), Some("4".into())]
+        );
+        assert_eq!(
+            example_answers("## --- Day 2 ---\n\nThe answer is *8*.\n"),
+            [Some("8".into()), None]
+        );
+        assert_eq!(example_answers("no emphasis"), [None, None]);
+    }
+}
@@ -2,7 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::stats::Stats;
+use crate::PuzzleId;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -20,9 +21,11 @@ impl From<std::io::Error> for Error {
 
 #[derive(Clone)]
 pub struct Timings {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub puzzle: PuzzleId,
+    /// Timings of the parse phase, only present for two-phase solutions.
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
     pub total_nanos: f64,
 }
 
@@ -32,8 +35,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -60,23 +63,33 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    // the parse column is only shown if at least one solution has a separate parse phase.
+    let has_parse = timings.iter().any(|timing| timing.parse.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_parse {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(timing.puzzle);
+        let parse = if has_parse {
+            format!(" `{}` |", format_stats(timing.parse.as_ref()))
+        } else {
+            String::new()
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            "| [Day {}]({}) |{} `{}` | `{}` |",
+            timing.puzzle.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format_stats(timing.part_1.as_ref()),
+            format_stats(timing.part_2.as_ref())
         ));
     }
 
@@ -87,6 +100,13 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
+fn format_stats(stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) => format!("{:.1?} ± {:.1?}", stats.mean, stats.std_dev),
+        None => "-".into(),
+    }
+}
+
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -104,27 +124,47 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::template::stats::Stats;
+    use crate::{day, year, PuzzleId};
+
+    fn get_mock_stats(mean_millis: u64) -> Option<Stats> {
+        let mean = Duration::from_millis(mean_millis);
+        Some(Stats {
+            mean,
+            std_dev: Duration::from_millis(1),
+            min: mean,
+            median: mean,
+            p95: mean,
+            p99: mean,
+            max: mean,
+            outliers: 0,
+        })
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                puzzle: PuzzleId::new(year!(2023), day!(1)),
+                parse: None,
+                part_1: get_mock_stats(10),
+                part_2: get_mock_stats(20),
                 total_nanos: 3e+10,
             },
             Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                puzzle: PuzzleId::new(year!(2023), day!(2)),
+                parse: None,
+                part_1: get_mock_stats(30),
+                part_2: get_mock_stats(40),
                 total_nanos: 7e+10,
             },
             Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                puzzle: PuzzleId::new(year!(2023), day!(4)),
+                parse: None,
+                part_1: get_mock_stats(40),
+                part_2: get_mock_stats(50),
                 total_nanos: 9e+10,
             },
         ]
@@ -172,9 +212,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms ± 1.0ms` | `20.0ms ± 1.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms ± 1.0ms` | `40.0ms ± 1.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms ± 1.0ms` | `50.0ms ± 1.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +223,50 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse_phase() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[1].parse = get_mock_stats(5);
+        update_content(&mut s, timings, 195.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms ± 1.0ms` | `20.0ms ± 1.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `5.0ms ± 1.0ms` | `30.0ms ± 1.0ms` | `40.0ms ± 1.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40.0ms ± 1.0ms` | `50.0ms ± 1.0ms` |",
+            "",
+            "**Total: 195.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_missing_part() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[2].part_2 = None;
+        update_content(&mut s, timings, 140.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms ± 1.0ms` | `20.0ms ± 1.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms ± 1.0ms` | `40.0ms ± 1.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms ± 1.0ms` | `-` |",
+            "",
+            "**Total: 140.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
@@ -0,0 +1,499 @@
+/// Machine-readable records of solution runs.
+/// Solution binaries emit these when invoked with `--format json` or `--format ndjson`.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::allocations::AllocStats;
+use crate::template::answers;
+use crate::template::stats::Stats;
+use crate::{Day, Part, PuzzleId, Year};
+
+/// The output format of the runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Human readable output, decorated with ANSI escape codes.
+    #[default]
+    Text,
+    /// A single JSON array containing all records, printed after the run finished.
+    Json,
+    /// One JSON object per line, printed as soon as a record is available.
+    Ndjson,
+}
+
+impl Format {
+    #[must_use]
+    pub fn is_structured(self) -> bool {
+        self != Format::Text
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => f.write_str("text"),
+            Format::Json => f.write_str("json"),
+            Format::Ndjson => f.write_str("ndjson"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The phase of a solution that a [`PartReport`] was recorded for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Parsing the input of a two-phase solution, shared by both parts.
+    Parse,
+    Part(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.write_str("Parse"),
+            Phase::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// The error of a part whose solution returned [`None`].
+pub const NO_ANSWER: &str = "no answer";
+
+/// The outcome of one implementation of a part, for solutions that register variants of their parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantReport {
+    /// The name of the function, e.g. `part_two_brute_force`.
+    pub name: String,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    pub error: Option<String>,
+}
+
+/// The outcome of running a single phase of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub puzzle: PuzzleId,
+    pub phase: Phase,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    /// Distribution of the samples, only present for benchmarked parts.
+    pub stats: Option<Stats>,
+    /// Allocations of the first run, only present when run with `--alloc`.
+    pub alloc: Option<AllocStats>,
+    /// The accepted answer of this part, if known.
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    /// Every implementation of this part, starting with the main one. Empty unless the solution registered variants.
+    pub variants: Vec<VariantReport>,
+}
+
+impl PartReport {
+    /// Why a part has no answer, unless the solution simply did not return one.
+    #[must_use]
+    pub fn failure(&self) -> Option<&str> {
+        self.error.as_deref().filter(|&error| error != NO_ANSWER)
+    }
+
+    /// Whether the answer matches the accepted answer, [`None`] if there is no accepted answer.
+    #[must_use]
+    pub fn is_correct(&self) -> Option<bool> {
+        let expected = self.expected.as_deref()?;
+        Some(
+            self.answer
+                .as_deref()
+                .is_some_and(|answer| answers::matches(answer, expected)),
+        )
+    }
+
+    #[must_use]
+    pub fn is_benchmarked(&self) -> bool {
+        self.samples > 1
+    }
+
+    /// Serializes the report into a single-line JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"year":{},"day":{},"phase":"{}","part":{},"answer":{},"duration_nanos":{},"samples":{},"stats":{},"alloc":{},"expected":{},"correct":{},"error":{},"variants":[{}]}}"#,
+            self.puzzle.year.into_inner(),
+            self.puzzle.day.into_inner(),
+            match self.phase {
+                Phase::Parse => "parse",
+                Phase::Part(_) => "solve",
+            },
+            match self.phase {
+                Phase::Parse => "null".into(),
+                Phase::Part(part) => part.to_string(),
+            },
+            json_string(self.answer.as_deref()),
+            self.duration.as_nanos(),
+            self.samples,
+            self.stats
+                .as_ref()
+                .map_or_else(|| "null".into(), stats_to_json),
+            self.alloc
+                .as_ref()
+                .map_or_else(|| "null".into(), alloc_to_json),
+            json_string(self.expected.as_deref()),
+            self.is_correct()
+                .map_or_else(|| "null".into(), |correct| correct.to_string()),
+            json_string(self.error.as_deref()),
+            self.variants
+                .iter()
+                .map(variant_to_json)
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+    }
+
+    /// Parses a report previously serialized with [`PartReport::to_json`].
+    pub fn from_json(s: &str) -> Result<Self, ReportParseError> {
+        let value: JsonValue = s.parse().map_err(|_| ReportParseError::Json)?;
+        Self::from_json_value(&value)
+    }
+
+    /// Parses a report from an already parsed JSON value, e.g. an item of a JSON array.
+    pub fn from_json_value(value: &JsonValue) -> Result<Self, ReportParseError> {
+        let object: &HashMap<String, JsonValue> = value.get().ok_or(ReportParseError::Json)?;
+
+        let number = |key: &'static str| number_field(object, key);
+
+        let string = |key: &'static str| -> Result<Option<String>, ReportParseError> {
+            match object.get(key) {
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(JsonValue::Null) | None => Ok(None),
+                Some(_) => Err(ReportParseError::Field(key)),
+            }
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Day::new(number("day")? as u8).ok_or(ReportParseError::Field("day"))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = Year::new(number("year")? as u16).ok_or(ReportParseError::Field("year"))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let phase = match string("phase")?.as_deref() {
+            Some("parse") => Phase::Parse,
+            Some("solve") | None => Phase::Part(
+                Part::new(number("part")? as u8).ok_or(ReportParseError::Field("part"))?,
+            ),
+            Some(_) => return Err(ReportParseError::Field("phase")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Self {
+            puzzle: PuzzleId::new(year, day),
+            phase,
+            answer: string("answer")?,
+            duration: Duration::from_nanos(number("duration_nanos")? as u64),
+            samples: number("samples")? as u128,
+            stats: match object.get("stats") {
+                Some(JsonValue::Object(stats)) => Some(stats_from_json(stats)?),
+                Some(JsonValue::Null) | None => None,
+                Some(_) => return Err(ReportParseError::Field("stats")),
+            },
+            alloc: match object.get("alloc") {
+                Some(JsonValue::Object(alloc)) => Some(alloc_from_json(alloc)?),
+                Some(JsonValue::Null) | None => None,
+                Some(_) => return Err(ReportParseError::Field("alloc")),
+            },
+            expected: string("expected")?,
+            error: string("error")?,
+            variants: match object.get("variants") {
+                Some(JsonValue::Array(items)) => items
+                    .iter()
+                    .map(variant_from_json)
+                    .collect::<Result<_, _>>()?,
+                Some(JsonValue::Null) | None => vec![],
+                Some(_) => return Err(ReportParseError::Field("variants")),
+            },
+        })
+    }
+}
+
+fn stats_to_json(stats: &Stats) -> String {
+    format!(
+        r#"{{"mean_nanos":{},"std_dev_nanos":{},"min_nanos":{},"median_nanos":{},"p95_nanos":{},"p99_nanos":{},"max_nanos":{},"outliers":{}}}"#,
+        stats.mean.as_nanos(),
+        stats.std_dev.as_nanos(),
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.p95.as_nanos(),
+        stats.p99.as_nanos(),
+        stats.max.as_nanos(),
+        stats.outliers,
+    )
+}
+
+fn stats_from_json(object: &HashMap<String, JsonValue>) -> Result<Stats, ReportParseError> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = |key: &'static str| {
+        number_field(object, key).map(|nanos| Duration::from_nanos(nanos as u64))
+    };
+
+    Ok(Stats {
+        mean: duration("mean_nanos")?,
+        std_dev: duration("std_dev_nanos")?,
+        min: duration("min_nanos")?,
+        median: duration("median_nanos")?,
+        p95: duration("p95_nanos")?,
+        p99: duration("p99_nanos")?,
+        max: duration("max_nanos")?,
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        outliers: number_field(object, "outliers")? as usize,
+    })
+}
+
+fn alloc_to_json(alloc: &AllocStats) -> String {
+    format!(
+        r#"{{"allocations":{},"bytes":{},"peak_bytes":{}}}"#,
+        alloc.allocations, alloc.bytes, alloc.peak_bytes,
+    )
+}
+
+fn alloc_from_json(object: &HashMap<String, JsonValue>) -> Result<AllocStats, ReportParseError> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = |key: &'static str| number_field(object, key).map(|n| n as u64);
+
+    Ok(AllocStats {
+        allocations: count("allocations")?,
+        bytes: count("bytes")?,
+        peak_bytes: count("peak_bytes")?,
+    })
+}
+
+fn variant_to_json(variant: &VariantReport) -> String {
+    format!(
+        r#"{{"name":{},"answer":{},"duration_nanos":{},"samples":{},"error":{}}}"#,
+        json_string(Some(&variant.name)),
+        json_string(variant.answer.as_deref()),
+        variant.duration.as_nanos(),
+        variant.samples,
+        json_string(variant.error.as_deref()),
+    )
+}
+
+fn variant_from_json(value: &JsonValue) -> Result<VariantReport, ReportParseError> {
+    let object: &HashMap<String, JsonValue> =
+        value.get().ok_or(ReportParseError::Field("variants"))?;
+
+    let string = |key: &'static str| -> Result<Option<String>, ReportParseError> {
+        match object.get(key) {
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(JsonValue::Null) | None => Ok(None),
+            Some(_) => Err(ReportParseError::Field(key)),
+        }
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(VariantReport {
+        name: string("name")?.ok_or(ReportParseError::Field("name"))?,
+        answer: string("answer")?,
+        duration: Duration::from_nanos(number_field(object, "duration_nanos")? as u64),
+        samples: number_field(object, "samples")? as u128,
+        error: string("error")?,
+    })
+}
+
+fn number_field(
+    object: &HashMap<String, JsonValue>,
+    key: &'static str,
+) -> Result<f64, ReportParseError> {
+    object
+        .get(key)
+        .and_then(|v| v.get::<f64>())
+        .copied()
+        .ok_or(ReportParseError::Field(key))
+}
+
+fn json_string(s: Option<&str>) -> String {
+    match s {
+        // stringifying a plain string value can not fail.
+        Some(s) => JsonValue::String(s.into()).stringify().unwrap(),
+        None => "null".into(),
+    }
+}
+
+/// Prints a list of reports as a single JSON array.
+pub fn print_json(reports: &[PartReport]) {
+    let items: Vec<String> = reports.iter().map(PartReport::to_json).collect();
+    println!("[{}]", items.join(","));
+}
+
+/// An error which can be returned when parsing a [`PartReport`].
+#[derive(Debug)]
+pub enum ReportParseError {
+    Json,
+    Field(&'static str),
+}
+
+impl Error for ReportParseError {}
+
+impl Display for ReportParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportParseError::Json => f.write_str("record is not a JSON object"),
+            ReportParseError::Field(key) => write!(f, "record has a missing or invalid `{key}`"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Format, PartReport, Phase, VariantReport};
+    use crate::template::allocations::AllocStats;
+    use crate::template::stats::Stats;
+    use crate::{day, year, Part, PuzzleId};
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(3)),
+            phase: Phase::Part(Part::TWO),
+            answer: Some("line \"one\"\nline two".into()),
+            duration: Duration::from_nanos(74130),
+            samples: 100,
+            stats: None,
+            alloc: None,
+            expected: None,
+            error: None,
+            variants: vec![],
+        }
+    }
+
+    #[test]
+    fn serializes_report() {
+        assert_eq!(
+            get_mock_report().to_json(),
+            r#"{"year":2023,"day":3,"phase":"solve","part":2,"answer":"line \"one\"\nline two","duration_nanos":74130,"samples":100,"stats":null,"alloc":null,"expected":null,"correct":null,"error":null,"variants":[]}"#
+        );
+    }
+
+    #[test]
+    fn roundtrips_report() {
+        let report = get_mock_report();
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+
+        let failed = PartReport {
+            answer: None,
+            error: Some("no answer".into()),
+            ..report
+        };
+        assert_eq!(PartReport::from_json(&failed.to_json()).unwrap(), failed);
+
+        let benchmarked = PartReport {
+            stats: Stats::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(30)]),
+            ..get_mock_report()
+        };
+        assert_eq!(
+            PartReport::from_json(&benchmarked.to_json()).unwrap(),
+            benchmarked
+        );
+
+        let profiled = PartReport {
+            alloc: Some(AllocStats {
+                allocations: 3,
+                bytes: 4096,
+                peak_bytes: 2048,
+            }),
+            ..get_mock_report()
+        };
+        assert_eq!(
+            PartReport::from_json(&profiled.to_json()).unwrap(),
+            profiled
+        );
+
+        let compared = PartReport {
+            error: Some("variant part_two_brute_force disagrees".into()),
+            variants: vec![
+                VariantReport {
+                    name: "part_two".into(),
+                    answer: Some("46".into()),
+                    duration: Duration::from_nanos(120),
+                    samples: 1,
+                    error: None,
+                },
+                VariantReport {
+                    name: "part_two_brute_force".into(),
+                    answer: None,
+                    duration: Duration::from_nanos(3000),
+                    samples: 1,
+                    error: Some("panicked: overflow".into()),
+                },
+            ],
+            ..get_mock_report()
+        };
+        assert_eq!(
+            PartReport::from_json(&compared.to_json()).unwrap(),
+            compared
+        );
+
+        let parse = PartReport {
+            phase: Phase::Parse,
+            answer: None,
+            ..get_mock_report()
+        };
+        assert_eq!(PartReport::from_json(&parse.to_json()).unwrap(), parse);
+
+        let verified = PartReport {
+            answer: Some("42".into()),
+            expected: Some("43".into()),
+            ..get_mock_report()
+        };
+        assert!(verified
+            .to_json()
+            .contains(r#""expected":"43","correct":false"#));
+        assert_eq!(
+            PartReport::from_json(&verified.to_json()).unwrap(),
+            verified
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_records() {
+        assert!(PartReport::from_json("Part 1: 42 (1.0ms)").is_err());
+        assert!(PartReport::from_json(r#"{"year":2023,"day":0,"part":1}"#).is_err());
+    }
+
+    #[test]
+    fn parses_format() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("ndjson".parse::<Format>().unwrap(), Format::Ndjson);
+        assert_eq!("text".parse::<Format>().unwrap(), Format::Text);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -1,63 +1,540 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::allocations::{self, AllocStats};
+use crate::template::input::Input;
+use crate::template::report::{self, Format, PartReport, Phase, VariantReport, NO_ANSWER};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, Outcome, Submission};
+use crate::template::worker::{self, Progress};
+use crate::template::{answers, backend, Solution, ANSI_ITALIC, ANSI_RESET};
+use crate::{Part, PuzzleId};
+use std::cell::Cell;
+use std::error::Error;
 use std::fmt::Display;
+use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Options that control how the parts of a solution are run.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Benchmark every part instead of running it once.
+    pub time: bool,
+    /// Number of warm-up iterations before benchmarking, defaults to a tenth of the measured iterations.
+    pub warmup: Option<u128>,
+    pub format: Format,
+    /// The input to run against, defaults to the puzzle input.
+    pub input: Input,
+    /// Only run this part, both parts are run otherwise.
+    pub part: Option<Part>,
+    /// Count the allocations of the first run of each part, requires the `alloc` feature.
+    pub alloc: bool,
+    /// Wall-clock limit for the first run of each part, parts are not limited if it is not set.
+    pub timeout: Option<Duration>,
+    /// The part whose answer should be submitted to Advent of Code.
+    pub submit: Option<Part>,
+    /// Submit even if earlier submissions show that the answer is wrong.
+    pub force: bool,
+    /// Don't print anything while running, the caller renders the returned reports instead.
+    pub quiet: bool,
+    /// Compare answers against the accepted answers in `data/<year>/answers`, only done for the puzzle input.
+    pub verify: bool,
+}
+
+impl RunOptions {
+    /// Reads the options from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let options = (|| -> Result<Self, pico_args::Error> {
+            Ok(Self {
+                time: args.contains("--time"),
+                warmup: args.opt_value_from_str("--warmup")?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                input: parse_input(&mut args)?,
+                part: args.opt_value_from_str("--part")?,
+                timeout: parse_timeout(&mut args)?,
+                alloc: args.contains("--alloc"),
+                submit: args.opt_value_from_str("--submit")?,
+                force: args.contains("--force"),
+                quiet: false,
+                verify: true,
+            })
+        })();
+
+        let options = options.unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input: {e}. Format: cargo solve 1 --submit 1");
+            process::exit(1);
+        });
+
+        // solution binaries run directly are not re-run with the feature, unlike the `cargo solve` command.
+        if options.alloc && !allocations::is_enabled() {
+            eprintln!("Allocations are only counted by the counting allocator, rebuild with `--features alloc`.");
+            process::exit(1);
+        }
+
+        options
+    }
+
+    /// Whether a part of a puzzle should be run, i.e. the puzzle has it and no other part was selected with `--part`.
+    #[must_use]
+    pub fn runs(&self, puzzle: PuzzleId, part: Part) -> bool {
+        part.exists_on(puzzle.day) && self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// Parses `--input <path>`, `--input -` (stdin) and `--example [k]`.
+/// Without them, solutions run against the puzzle input.
+pub fn parse_input(args: &mut pico_args::Arguments) -> Result<Input, pico_args::Error> {
+    if let Some(input) = args.opt_value_from_str("--input")? {
+        return Ok(input);
+    }
+
+    // the suffix of `--example` is optional, a missing or non-numeric value is left for other options.
+    match args.opt_value_from_str("--example") {
+        Ok(Some(k)) => Ok(Input::Example(Some(k))),
+        Ok(None) => Ok(Input::Puzzle),
+        Err(_) => {
+            args.contains("--example");
+            Ok(Input::Example(None))
+        }
+    }
+}
+
+/// Parses `--timeout <seconds>`.
+pub fn parse_timeout(
+    args: &mut pico_args::Arguments,
+) -> Result<Option<Duration>, pico_args::Error> {
+    args.opt_value_from_fn("--timeout", |s| s.parse().map(Duration::from_secs))
+}
+
+/// Entry point of solution binaries: runs a solution against the selected input.
+pub fn run_main(solution: &'static impl Solution) {
+    let options = RunOptions::from_args();
+    let reports = run_solution(solution, &options);
+    finish(&reports, &options);
+    exit_on_failure(&reports);
+}
+
+/// Reads the input selected in `options` and runs a solution against it on a worker thread, see [`worker::run`].
+/// If the input can't be read, every selected part fails with the reason instead.
+pub fn run_solution(solution: &'static dyn Solution, options: &RunOptions) -> Vec<PartReport> {
+    match options.input.read(solution.puzzle()) {
+        Ok(input) => worker::run(solution, &input, options),
+        Err(e) => fail_parts(solution.puzzle(), &e.to_string(), options),
+    }
+}
+
+/// Reports every selected part as failed without running it.
+pub fn fail_parts(puzzle: PuzzleId, reason: &str, options: &RunOptions) -> Vec<PartReport> {
+    Part::both()
+        .into_iter()
+        .filter(|&part| options.runs(puzzle, part))
+        .map(|part| fail_part(puzzle, part, reason, options))
+        .collect()
+}
+
+/// Reports a part as failed, e.g. because it timed out.
+pub fn fail_part(puzzle: PuzzleId, part: Part, reason: &str, options: &RunOptions) -> PartReport {
+    let report = PartReport {
+        puzzle,
+        phase: Phase::Part(part),
+        answer: None,
+        duration: Duration::ZERO,
+        samples: 1,
+        stats: None,
+        alloc: None,
+        expected: (options.verify && options.input.is_puzzle())
+            .then(|| answers::load(puzzle, part))
+            .flatten(),
+        error: Some(reason.into()),
+        variants: vec![],
+    };
+
+    if !options.quiet {
+        emit_report(&report, options.format);
+    }
+
+    report
+}
+
+/// Return types of solution parts: an [`Option`], or a [`Result`] whose error explains why there is no answer.
+pub trait PartResult {
+    /// Converts the result into the answer, or the reason why there is none.
+    fn into_answer(self) -> Result<String, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn into_answer(self) -> Result<String, String> {
+        self.map(|answer| answer.to_string())
+            .ok_or_else(|| NO_ANSWER.into())
+    }
+}
+
+/// Errors are printed with their chain of causes, e.g. `invalid game: invalid digit found in string`.
+/// Any error that converts into a boxed [`Error`] can be returned, including [`String`] and `&str`.
+impl<'a, T: Display, E: Into<Box<dyn Error + 'a>>> PartResult for Result<T, E> {
+    fn into_answer(self) -> Result<String, String> {
+        self.map(|answer| answer.to_string())
+            .map_err(|e| error_chain(&*e.into()))
+    }
+}
+
+/// Joins an error and its sources with `: `.
+fn error_chain(error: &dyn Error) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    chain
+}
+
+/// A named implementation of a part, registered with the `variants` argument of [`solution!`](crate::solution).
+pub type Variant<I, R> = (&'static str, fn(I) -> R);
+
+/// Runs a part of a solution, returns [`None`] without running it if another part was selected.
+/// A panic of the part is caught and reported as its error, so that the other part still runs.
+/// Also returns [`None`] if the runner abandoned the part after a timeout.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: Part,
+    options: &RunOptions,
+) -> Option<PartReport> {
+    run_part_variants(("", func), &[], input, puzzle, part, options)
+}
+
+/// Runs a part like [`run_part`], then runs each of its `variants` against the same input and times them the same way.
+/// The answer of the main implementation `(name, func)` is the one that is verified and submitted.
+/// If a variant returns a different answer, the part fails.
+pub fn run_part_variants<I: Clone, R: PartResult>(
+    (name, func): (&str, impl Fn(I) -> R),
+    variants: &[Variant<I, R>],
+    input: I,
+    puzzle: PuzzleId,
+    part: Part,
+    options: &RunOptions,
+) -> Option<PartReport> {
+    if !options.runs(puzzle, part) || !worker::notify(Progress::Started(Phase::Part(part))) {
+        return None;
+    }
+
+    let format = options.format;
     let part_str = format!("Part {part}");
+    let abandoned = Cell::new(false);
+    let timer = Instant::now();
+
+    let timed = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(func, input.clone(), options, |result| {
+            abandoned.set(!worker::notify(Progress::Ran));
+            let answer = result.into_answer();
+            if !format.is_structured() && !options.quiet && !abandoned.get() {
+                print_result(&answer.clone().ok(), &part_str, "", "");
+            }
+            (answer, !abandoned.get())
+        })
+    }));
+
+    if abandoned.get() {
+        return None;
+    }
+
+    let (result, duration, samples, stats, alloc) = match timed {
+        Ok(timed) => timed,
+        Err(payload) => {
+            worker::notify(Progress::Ran);
+            let message = worker::panic_message(&*payload);
+            (
+                Err(format!("panicked: {message}")),
+                timer.elapsed(),
+                1,
+                None,
+                None,
+            )
+        }
+    };
+
+    let mut report = PartReport {
+        puzzle,
+        phase: Phase::Part(part),
+        answer: result.as_ref().ok().cloned(),
+        duration,
+        samples,
+        stats,
+        alloc,
+        expected: (options.verify && options.input.is_puzzle())
+            .then(|| answers::load(puzzle, part))
+            .flatten(),
+        error: result.as_ref().err().cloned(),
+        variants: vec![],
+    };
+
+    if !variants.is_empty() {
+        report.variants.push(VariantReport {
+            name: name.into(),
+            answer: report.answer.clone(),
+            duration,
+            samples,
+            error: report.error.clone(),
+        });
+
+        for &(name, variant) in variants {
+            // the runner keeps the variants that finished if this one times out.
+            if !worker::notify(Progress::Report(Box::new(report.clone()))) {
+                return None;
+            }
+
+            report
+                .variants
+                .push(run_variant(name, variant, input.clone(), options)?);
+        }
+
+        if let Some(disagreement) = find_disagreement(&report.variants) {
+            report.error = Some(disagreement);
+        }
+    }
+
+    if !worker::notify(Progress::Report(Box::new(report.clone()))) {
+        return None;
+    }
+
+    if !options.quiet {
+        emit_report(&report, format);
+    }
+
+    if let Ok(result) = result {
+        if options.submit == Some(part) {
+            if !options.input.is_puzzle() {
+                eprintln!("Not submitting {result}: it was not computed from the puzzle input.");
+            } else if let Some(failure) = report.failure() {
+                eprintln!("Not submitting {result}: {failure}.");
+            } else {
+                submit_result(result, puzzle, part, options.force);
+            }
+        }
+    }
+
+    Some(report)
+}
+
+/// Runs a variant of a part, returns [`None`] if the runner abandoned it after a timeout.
+fn run_variant<I: Clone, R: PartResult>(
+    name: &str,
+    func: fn(I) -> R,
+    input: I,
+    options: &RunOptions,
+) -> Option<VariantReport> {
+    if !worker::notify(Progress::Variant(name.into())) {
+        return None;
+    }
+
+    let options = RunOptions {
+        quiet: true,
+        alloc: false,
+        ..options.clone()
+    };
+    let abandoned = Cell::new(false);
+    let timer = Instant::now();
+
+    let timed = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(func, input, &options, |result| {
+            abandoned.set(!worker::notify(Progress::Ran));
+            (result.into_answer(), !abandoned.get())
+        })
+    }));
+
+    if abandoned.get() {
+        return None;
+    }
+
+    let (result, duration, samples) = match timed {
+        Ok((result, duration, samples, _, _)) => (result, duration, samples),
+        Err(payload) => {
+            worker::notify(Progress::Ran);
+            let message = worker::panic_message(&*payload);
+            (Err(format!("panicked: {message}")), timer.elapsed(), 1)
+        }
+    };
+
+    Some(VariantReport {
+        name: name.into(),
+        answer: result.as_ref().ok().cloned(),
+        duration,
+        samples,
+        error: result.err(),
+    })
+}
+
+/// Names the variants whose answer differs from the answer of the main implementation, which comes first.
+fn find_disagreement(variants: &[VariantReport]) -> Option<String> {
+    let (main, others) = variants.split_first()?;
+    let names: Vec<&str> = others
+        .iter()
+        .filter(|variant| variant.answer != main.answer)
+        .map(|variant| variant.name.as_str())
+        .collect();
+
+    match names[..] {
+        [] => None,
+        [name] => Some(format!("variant {name} disagrees")),
+        _ => Some(format!("variants {} disagree", names.join(", "))),
+    }
+}
+
+/// Records that a variant of a part did not finish, e.g. because it timed out, and reports the part as failed.
+/// Variants that finished before it are still compared with the main implementation.
+pub fn fail_variant(report: &mut PartReport, name: &str, reason: &str, options: &RunOptions) {
+    let failure = format!("variant {name}: {reason}");
+    report.error = Some(match find_disagreement(&report.variants) {
+        Some(disagreement) => format!("{disagreement}, {failure}"),
+        None => failure,
+    });
+
+    report.variants.push(VariantReport {
+        name: name.into(),
+        answer: None,
+        duration: Duration::ZERO,
+        samples: 1,
+        error: Some(reason.into()),
+    });
+
+    if !options.quiet {
+        emit_report(report, options.format);
+    }
+}
+
+/// Runs the parse phase of a two-phase solution and returns the parsed input alongside its timings.
+/// The parsed input is handed to both parts, so their timings no longer include parsing.
+pub fn run_parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (T, PartReport) {
+    let format = options.format;
+    // a restarted worker parses again, the parse phase was shown by the first one.
+    let quiet = options.quiet || worker::is_restarted();
+
+    worker::notify(Progress::Started(Phase::Parse));
+
+    let (parsed, duration, samples, stats, alloc) = run_timed(func, input, options, |parsed| {
+        let running = worker::notify(Progress::Ran);
+        if running && !format.is_structured() && !quiet {
+            print!("{}:", Phase::Parse);
+            let _ = stdout().flush();
+        }
+        (parsed, running)
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let report = PartReport {
+        puzzle,
+        phase: Phase::Parse,
+        answer: None,
+        duration,
+        samples,
+        stats,
+        alloc,
+        expected: None,
+        error: None,
+        variants: vec![],
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    worker::notify(Progress::Report(Box::new(report.clone())));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if !quiet {
+        emit_report(&report, format);
+    }
+
+    (parsed, report)
+}
+
+/// Prints a report as soon as it is available, JSON reports are deferred to [`finish`].
+pub fn emit_report(report: &PartReport, format: Format) {
+    match format {
+        Format::Text => print_report(report),
+        Format::Ndjson => println!("{}", report.to_json()),
+        // JSON reports are printed as a whole once all parts ran, see `finish`.
+        Format::Json => {}
+    }
+}
+
+/// Prints the reports of all parts in formats that can only be emitted once the run is complete.
+pub fn finish(reports: &[PartReport], options: &RunOptions) {
+    if options.format == Format::Json {
+        report::print_json(reports);
+    }
+}
+
+/// Exits with a non-zero status if any part failed, e.g. because it panicked or timed out.
+/// Parts that returned no answer are not failures, they are simply not solved yet.
+pub fn exit_on_failure(reports: &[PartReport]) {
+    if reports.iter().any(|report| report.failure().is_some()) {
+        process::exit(1);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+///
+/// `hook` converts the result of the first execution, e.g. into the answer, and returns whether to continue.
+/// A part that was abandoned after a timeout is not benched, so that it stops as soon as possible.
+fn run_timed<I: Clone, T, U>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+    hook: impl FnOnce(T) -> (U, bool),
+) -> (U, Duration, u128, Option<Stats>, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (result, alloc) = if options.alloc {
+        let (result, alloc) = allocations::measure(|| func(input.clone()));
+        (result, Some(alloc))
+    } else {
+        (func(input.clone()), None)
+    };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let (result, running) = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if options.time && running {
+        let (stats, samples) = bench(func, input, &base_time, options);
+        (result, stats.mean, samples, Some(stats), alloc)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, 1, None, alloc)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Benchmarks a function after a warm-up phase.
+/// The number of warm-up iterations defaults to a tenth of the measured iterations and can be set with `--warmup <n>`.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &RunOptions,
+) -> (Stats, u128) {
+    if !options.format.is_structured() && !options.quiet {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let warmup_iterations = options.warmup.unwrap_or(bench_iterations / 10);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -65,40 +542,117 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
-        func(cloned);
+        // black-boxing keeps the optimizer from eliding calls with unused results.
+        black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
     }
 
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        // the loop above runs at least 10 times, so there always are samples.
+        Stats::from_samples(&timers).unwrap(),
         bench_iterations,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Prints the final result line of a part in text format.
+pub fn print_report(report: &PartReport) {
+    let duration_str = format_duration(&report.duration, report.samples, report.stats.as_ref());
+
+    match report.phase {
+        Phase::Parse => println!("\r{}:{duration_str}", report.phase),
+        Phase::Part(_) => print_result(
+            &report.answer,
+            &report.phase.to_string(),
+            &match report.failure() {
+                Some(failure) => format!(" {failure}{}", format_check(report)),
+                None => format_check(report),
+            },
+            &duration_str,
+        ),
+    }
+
+    if let Some(stats) = &report.stats {
+        println!("        {ANSI_ITALIC}{stats}{ANSI_RESET}");
+    }
+
+    if let Some(alloc) = &report.alloc {
+        println!("        {ANSI_ITALIC}{alloc}{ANSI_RESET}");
+    }
+
+    print_variants(&report.variants);
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&Stats>) -> String {
+    match stats {
+        _ if samples == 1 => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} ± {:.1?} @ {samples} samples)",
+            stats.std_dev
+        ),
+        None => format!(" ({duration:.1?} @ {samples} samples)"),
+    }
+}
+
+/// Prints the timings of the variants of a part side by side, relative to the main implementation.
+/// Variants whose answer differs from the main implementation are marked with `✘`.
+fn print_variants(variants: &[VariantReport]) {
+    let Some(main) = variants.first() else {
+        return;
+    };
+
+    let width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+
+    for variant in variants {
+        let duration = format!("{:.1?}", variant.duration);
+
+        #[allow(clippy::cast_precision_loss)]
+        let factor = match variant.answer {
+            Some(_) => format!(
+                "{:.2}×",
+                variant.duration.as_nanos() as f64 / main.duration.as_nanos().max(1) as f64
+            ),
+            None => "-".into(),
+        };
+
+        let result = match (&variant.answer, &variant.error) {
+            (Some(answer), _) if answer.contains('\n') => "▼".into(),
+            (Some(answer), _) => answer.clone(),
+            (None, Some(error)) => format!("✖ {error}"),
+            (None, None) => "✖".into(),
+        };
+
+        let check = if variant.answer == main.answer {
+            ""
+        } else {
+            " ✘"
+        };
+
+        println!(
+            "        {ANSI_ITALIC}{:width$}  {duration:>10}  {factor:>8}  {result}{check}{ANSI_RESET}",
+            variant.name
+        );
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Formats the result of comparing a part against its accepted answer.
+fn format_check(report: &PartReport) -> String {
+    match (report.is_correct(), report.expected.as_deref()) {
+        (Some(true), _) => " ✔".into(),
+        (Some(false), Some(expected)) if !expected.contains('\n') => {
+            format!(" ✘ (expected {expected})")
+        }
+        (Some(false), _) => " ✘".into(),
+        (None, _) => String::new(),
+    }
+}
+
+fn print_result<T: Display>(result: &Option<T>, part: &str, check: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼{check} {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -107,7 +661,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{check}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -121,47 +675,183 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{check}             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Try to submit one part of the solution if aoc-cli is installed.
+fn submit_result<T: Display>(result: T, puzzle: PuzzleId, part: Part, force: bool) {
+    let backend = backend::select().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+    let answer = result.to_string();
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if let Err(refusal) = submissions::check(&submissions::load(puzzle, part), &answer) {
+        if force {
+            eprintln!("Submitting anyway: {refusal}.");
+        } else {
+            eprintln!("Not submitting {answer}: {refusal}. Append `--force` to submit anyway.");
+            return;
+        }
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    wait_for_cooldown();
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let response = match backend.submit(puzzle, part, &answer) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    if let Some(wait) = submissions::parse_wait(&response) {
+        if let Err(e) = submissions::start_cooldown(wait) {
+            eprintln!("Failed to store submission cooldown: {e}");
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let Some(outcome) = Outcome::from_response(&response) else {
+        eprintln!("Could not detect the outcome of the submission, it was not recorded.");
+        return;
+    };
+
+    if let Err(e) = submissions::record(puzzle, part, &Submission::new(outcome, &answer)) {
+        eprintln!("Failed to record submission: {e}");
+    }
+
+    if outcome == Outcome::Correct {
+        match answers::save(puzzle, part, &answer) {
+            Ok(()) => println!(
+                "🎄 Saved accepted answer to \"{}\".",
+                answers::get_path(puzzle, part).display()
+            ),
+            Err(e) => eprintln!("Failed to save accepted answer: {e}"),
+        }
+    }
+}
+
+/// Blocks until the submission cooldown stored by an earlier submission has passed, showing a countdown.
+fn wait_for_cooldown() {
+    let mut waited = false;
+
+    while let Some(remaining) = submissions::cooldown_remaining() {
+        let secs = remaining.as_secs();
+        print!(
+            "\r{ANSI_ITALIC}Waiting for the submission cooldown, {}:{:02} left...{ANSI_RESET} ",
+            secs / 60,
+            secs % 60
+        );
+        let _ = stdout().flush();
+        thread::sleep(Duration::from_secs(1));
+        waited = true;
+    }
+
+    if waited {
+        print!("\r\x1b[2K");
+        let _ = stdout().flush();
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    use std::time::Duration;
+
+    use super::{fail_variant, PartResult, RunOptions};
+    use crate::template::report::{PartReport, Phase, VariantReport};
+    use crate::{day, year, Part, PuzzleId};
+
+    /// An error caused by another error.
+    #[derive(Debug)]
+    struct GameError(std::num::ParseIntError);
+
+    impl Display for GameError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid game")
+        }
+    }
+
+    impl Error for GameError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(42).into_answer(), Ok("42".into()));
+        assert_eq!(None::<u32>.into_answer(), Err("no answer".into()));
+        assert_eq!(Ok::<u32, String>(7).into_answer(), Ok("7".into()));
+        assert_eq!(
+            Err::<u32, _>("no start").into_answer(),
+            Err("no start".into())
+        );
+
+        let error = GameError("x".parse::<u32>().unwrap_err());
+        assert_eq!(
+            Err::<u32, _>(error).into_answer(),
+            Err("invalid game: invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn skips_parts_a_puzzle_does_not_have() {
+        let options = RunOptions::default();
+        let day_1 = PuzzleId::new(year!(2023), day!(1));
+        let day_25 = PuzzleId::new(year!(2023), day!(25));
+
+        assert!(options.runs(day_1, Part::TWO));
+        assert!(options.runs(day_25, Part::ONE));
+        assert!(!options.runs(day_25, Part::TWO));
+
+        let options = RunOptions {
+            part: Some(Part::TWO),
+            ..RunOptions::default()
+        };
+        assert!(!options.runs(day_1, Part::ONE));
+        assert!(options.runs(day_1, Part::TWO));
+    }
+
+    #[test]
+    fn keeps_disagreement_when_variant_fails() {
+        let variant = |name: &str, answer: &str| VariantReport {
+            name: name.into(),
+            answer: Some(answer.into()),
+            duration: Duration::from_nanos(1),
+            samples: 1,
+            error: None,
+        };
+
+        let mut report = PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            phase: Phase::Part(Part::TWO),
+            answer: Some("46".into()),
+            duration: Duration::from_nanos(1),
+            samples: 1,
+            stats: None,
+            alloc: None,
+            expected: None,
+            error: None,
+            variants: vec![variant("part_two", "46"), variant("part_two_alt", "47")],
+        };
+
+        let options = RunOptions {
+            quiet: true,
+            ..RunOptions::default()
+        };
+        fail_variant(&mut report, "part_two_brute_force", "timeout", &options);
+
+        assert_eq!(report.variants.len(), 3);
+        assert_eq!(
+            report.error.as_deref(),
+            Some("variant part_two_alt disagrees, variant part_two_brute_force: timeout")
+        );
+    }
 }
@@ -0,0 +1,41 @@
+/// Abstraction over solutions, so they can be run in-process from the main binary.
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::template::report::PartReport;
+use crate::template::runner::{self, RunOptions};
+use crate::PuzzleId;
+
+/// A solution for a single puzzle.
+/// The `solution!` macro implements this for every puzzle as `DaySolution`.
+pub trait Solution: Sync {
+    fn puzzle(&self) -> PuzzleId;
+
+    /// Runs all phases of the solution against `input`.
+    /// Reports are printed as they become available and returned once all phases ran.
+    fn run(&self, input: &str, options: &RunOptions) -> Vec<PartReport>;
+}
+
+/// Finds the solution for a given puzzle in a registry of solutions.
+#[must_use]
+pub fn find<'a>(solutions: &[&'a dyn Solution], puzzle: PuzzleId) -> Option<&'a dyn Solution> {
+    solutions
+        .iter()
+        .find(|solution| solution.puzzle() == puzzle)
+        .copied()
+}
+
+/// The error shown by commands that need the solution of a puzzle that was not scaffolded yet.
+#[must_use]
+pub fn missing_message(puzzle: PuzzleId) -> String {
+    format!(
+        "No solution found for day {} of {}. Type `cargo scaffold {} --year {}` to create one.",
+        puzzle.day, puzzle.year, puzzle.day, puzzle.year
+    )
+}
+
+/// Runs a solution against the input selected in `options`.
+/// A panic of the runner itself is printed by the panic hook and results in no reports.
+pub fn run(solution: &'static dyn Solution, options: &RunOptions) -> Vec<PartReport> {
+    panic::catch_unwind(AssertUnwindSafe(|| runner::run_solution(solution, options)))
+        .unwrap_or_default()
+}
@@ -0,0 +1,137 @@
+/// Summary statistics over the samples collected while benchmarking a solution part.
+use std::fmt::Display;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// Number of samples outside of the inner fences (1.5 × IQR below Q1 or above Q3).
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Computes statistics for a list of samples, returns [`None`] if the list is empty.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let len = sorted.len() as u128;
+        let mean = sorted.iter().sum::<u128>() / len;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|&x| (x as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / len as f64;
+
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let iqr = q3 - q1;
+        let lower_fence = q1.saturating_sub(iqr * 3 / 2);
+        let upper_fence = q3 + iqr * 3 / 2;
+
+        Some(Self {
+            mean: from_nanos(mean),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            min: from_nanos(sorted[0]),
+            median: from_nanos(percentile(&sorted, 50)),
+            p95: from_nanos(percentile(&sorted, 95)),
+            p99: from_nanos(percentile(&sorted, 99)),
+            max: from_nanos(sorted[sorted.len() - 1]),
+            outliers: sorted
+                .iter()
+                .filter(|&&x| x < lower_fence || x > upper_fence)
+                .count(),
+        })
+    }
+}
+
+/// Shows the distribution of samples as a single line, e.g. `min 1.0µs · median 1.2µs · ...`.
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.1?} · median {:.1?} · p95 {:.1?} · p99 {:.1?} · max {:.1?} · {} outlier{}",
+            self.min,
+            self.median,
+            self.p95,
+            self.p99,
+            self.max,
+            self.outliers,
+            if self.outliers == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty list.
+fn percentile(sorted: &[u128], p: usize) -> u128 {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.p99, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn distribution() {
+        let mut samples: Vec<u64> = (1..=100).collect();
+        samples.reverse();
+        let stats = Stats::from_samples(&nanos(&samples)).unwrap();
+
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.mean, Duration::from_nanos(50));
+        assert_eq!(stats.median, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.p99, Duration::from_nanos(99));
+        assert_eq!(stats.std_dev, Duration::from_nanos(29));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500, 11])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(500));
+    }
+}
@@ -0,0 +1,425 @@
+/// History of answers submitted to Advent of Code.
+/// Every submission is appended to `data/<year>/submissions/<day>-<part>.txt` as a line of `<unix timestamp> <outcome> <answer>`.
+/// The end of the current submission cooldown applies to all events and is stored in `data/cooldown.txt`.
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::template::datetime::now;
+use crate::template::get_data_path;
+use crate::{Part, PuzzleId};
+
+/// The verdict of Advent of Code on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// The answer is wrong, without a hint whether it is too high or too low.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked because another answer was submitted too recently.
+    TooRecent,
+    /// The part was already solved, so the answer was not checked.
+    AlreadyCompleted,
+}
+
+impl Outcome {
+    /// Detects the outcome from the response text printed by aoc-cli.
+    #[must_use]
+    pub fn from_response(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if response.contains("too high") {
+            Some(Outcome::TooHigh)
+        } else if response.contains("too low") {
+            Some(Outcome::TooLow)
+        } else if response.contains("not the right answer") {
+            Some(Outcome::Incorrect)
+        } else if response.contains("You gave an answer too recently") {
+            Some(Outcome::TooRecent)
+        } else if response.contains("Did you already complete it") {
+            Some(Outcome::AlreadyCompleted)
+        } else {
+            None
+        }
+    }
+
+    /// Whether Advent of Code checked the answer and rejected it.
+    #[must_use]
+    pub fn is_rejection(self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::TooRecent => "too_recent",
+            Outcome::AlreadyCompleted => "already_completed",
+        })
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = SubmissionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "too_recent" => Ok(Outcome::TooRecent),
+            "already_completed" => Ok(Outcome::AlreadyCompleted),
+            _ => Err(SubmissionParseError),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A single submitted answer and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn new(outcome: Outcome, answer: &str) -> Self {
+        Self {
+            timestamp: now(),
+            outcome,
+            answer: answer.trim().to_string(),
+        }
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.timestamp, self.outcome, self.answer)
+    }
+}
+
+impl FromStr for Submission {
+    type Err = SubmissionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(3, ' ');
+        let timestamp = fields.next().and_then(|x| x.parse().ok());
+        let outcome = fields.next().and_then(|x| x.parse().ok());
+
+        match (timestamp, outcome, fields.next()) {
+            (Some(timestamp), Some(outcome), Some(answer)) => Ok(Self {
+                timestamp,
+                outcome,
+                answer: answer.to_string(),
+            }),
+            _ => Err(SubmissionParseError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Submission`] or an [`Outcome`].
+#[derive(Debug)]
+pub struct SubmissionParseError;
+
+impl Error for SubmissionParseError {}
+
+impl Display for SubmissionParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a line of `<timestamp> <outcome> <answer>`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The reason an answer is not submitted, based on earlier submissions of the same part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The same answer was submitted and rejected before.
+    AlreadyRejected(Outcome),
+    /// The answer is not lower than an answer that was too high.
+    TooHigh(String),
+    /// The answer is not higher than an answer that was too low.
+    TooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected(outcome) => {
+                write!(
+                    f,
+                    "this answer was already submitted and rejected ({outcome})"
+                )
+            }
+            Refusal::TooHigh(bound) => write!(f, "{bound} was already too high"),
+            Refusal::TooLow(bound) => write!(f, "{bound} was already too low"),
+        }
+    }
+}
+
+/// Checks an answer against earlier submissions, returns a [`Refusal`] if it is known to be wrong.
+/// Bounds learned from "too high" and "too low" responses only apply to integer answers.
+pub fn check(history: &[Submission], answer: &str) -> Result<(), Refusal> {
+    let answer = answer.trim();
+
+    if let Some(rejected) = history
+        .iter()
+        .find(|s| s.outcome.is_rejection() && s.answer == answer)
+    {
+        return Err(Refusal::AlreadyRejected(rejected.outcome));
+    }
+
+    let Ok(value) = answer.parse::<i128>() else {
+        return Ok(());
+    };
+
+    let bound = |outcome: Outcome| {
+        history
+            .iter()
+            .filter(move |s| s.outcome == outcome)
+            .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+    };
+
+    if let Some((_, high)) = bound(Outcome::TooHigh).filter(|(x, _)| value >= *x).min() {
+        return Err(Refusal::TooHigh(high.clone()));
+    }
+
+    if let Some((_, low)) = bound(Outcome::TooLow).filter(|(x, _)| value <= *x).max() {
+        return Err(Refusal::TooLow(low.clone()));
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses how long to wait before the next submission from a response.
+/// Understands both "You have 1m 20s left to wait." and "Please wait one minute before trying again.".
+#[must_use]
+pub fn parse_wait(response: &str) -> Option<Duration> {
+    if let Some(start) = response.find("You have ") {
+        let rest = &response[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+
+        return rest[..end]
+            .split_whitespace()
+            .map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let lower = response.to_lowercase();
+    let start = lower.find("please wait ")?;
+    let mut words = lower[start + "please wait ".len()..].split_whitespace();
+
+    let minutes = match words.next()? {
+        "one" => 1,
+        value => value.parse().ok()?,
+    };
+
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| Duration::from_secs(minutes * 60))
+}
+
+#[must_use]
+pub fn get_cooldown_path() -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("cooldown.txt")
+}
+
+/// Stores that no answer may be submitted before `wait` has passed.
+pub fn start_cooldown(wait: Duration) -> io::Result<()> {
+    let path = get_cooldown_path();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, format!("{}\n", now() + wait.as_secs()))
+}
+
+/// The time left until the next answer may be submitted, [`None`] if there is no active cooldown.
+#[must_use]
+pub fn cooldown_remaining() -> Option<Duration> {
+    let until: u64 = fs::read_to_string(get_cooldown_path())
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+
+    until
+        .checked_sub(now())
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId, part: Part) -> PathBuf {
+    get_data_path(
+        puzzle.year,
+        "submissions",
+        &format!("{}-{part}.txt", puzzle.day),
+    )
+}
+
+/// Loads all previous submissions of a part, oldest first. Malformed lines are skipped.
+#[must_use]
+pub fn load(puzzle: PuzzleId, part: Part) -> Vec<Submission> {
+    fs::read_to_string(get_path(puzzle, part))
+        .map(|s| s.lines().filter_map(|line| line.parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Appends a submission to the history of a part.
+pub fn record(puzzle: PuzzleId, part: Part, submission: &Submission) -> io::Result<()> {
+    let path = get_path(puzzle, part);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{submission}")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{check, parse_wait, Outcome, Refusal, Submission};
+
+    fn submission(outcome: Outcome, answer: &str) -> Submission {
+        Submission {
+            timestamp: 0,
+            outcome,
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn detects_outcomes() {
+        assert_eq!(
+            Outcome::from_response("That's the right answer! You are one gold star closer to restoring snow operations."),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            Outcome::from_response(
+                "That's not the right answer; your answer is too high. If you're stuck, ..."
+            ),
+            Some(Outcome::TooHigh)
+        );
+        assert_eq!(
+            Outcome::from_response("That's not the right answer; your answer is too low."),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            Outcome::from_response("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            Some(Outcome::Incorrect)
+        );
+        assert_eq!(
+            Outcome::from_response("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 41s left to wait."),
+            Some(Outcome::TooRecent)
+        );
+        assert_eq!(
+            Outcome::from_response(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Some(Outcome::AlreadyCompleted)
+        );
+        assert_eq!(Outcome::from_response("something else"), None);
+    }
+
+    #[test]
+    fn roundtrips_submission() {
+        let submission = Submission {
+            timestamp: 1_701_388_800,
+            outcome: Outcome::TooHigh,
+            answer: "1234".into(),
+        };
+        assert_eq!(submission.to_string(), "1701388800 too_high 1234");
+        assert_eq!(
+            submission.to_string().parse::<Submission>().unwrap(),
+            submission
+        );
+        assert!("1701388800 maybe 1234".parse::<Submission>().is_err());
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let history = [
+            submission(Outcome::Incorrect, "abc"),
+            submission(Outcome::TooHigh, "500"),
+            submission(Outcome::TooHigh, "300"),
+            submission(Outcome::TooLow, "100"),
+            submission(Outcome::TooRecent, "200"),
+        ];
+
+        assert_eq!(
+            check(&history, "abc"),
+            Err(Refusal::AlreadyRejected(Outcome::Incorrect))
+        );
+        assert_eq!(
+            check(&history, "500"),
+            Err(Refusal::AlreadyRejected(Outcome::TooHigh))
+        );
+        assert_eq!(check(&history, "400"), Err(Refusal::TooHigh("300".into())));
+        assert_eq!(check(&history, "50"), Err(Refusal::TooLow("100".into())));
+        assert_eq!(check(&history, "200"), Ok(()));
+        assert_eq!(check(&history, "abd"), Ok(()));
+        assert_eq!(check(&[], "1"), Ok(()));
+    }
+
+    #[test]
+    fn parses_wait_time() {
+        assert_eq!(
+            parse_wait("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 41s left to wait. [Return to Day 1]"),
+            Some(Duration::from_secs(41))
+        );
+        assert_eq!(
+            parse_wait("You have 1m 20s left to wait."),
+            Some(Duration::from_secs(80))
+        );
+        assert_eq!(
+            parse_wait("That's not the right answer. Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_wait("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+}
@@ -0,0 +1,249 @@
+/// Runs solutions on worker threads, so that a hung part can be abandoned after a timeout
+/// and a panic outside of a part does not abort the runner.
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use crate::template::report::{PartReport, Phase};
+use crate::template::runner::{self, RunOptions};
+use crate::template::Solution;
+use crate::Part;
+
+/// Spawned threads default to a small stack, solutions should have the same stack as the main thread.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Progress of a solution, sent from the worker thread while it runs.
+pub enum Progress {
+    /// The first run of a phase started, it has to finish before the timeout.
+    Started(Phase),
+    /// The first run of a variant of the current part started, it has to finish before the timeout as well.
+    Variant(String),
+    /// The first run of a phase or variant finished, benchmarking it is not subject to the timeout.
+    Ran,
+    Report(Box<PartReport>),
+    /// The solution panicked outside of a part, e.g. while parsing.
+    Panicked(String),
+    Done,
+}
+
+struct Worker {
+    sender: Sender<Progress>,
+    /// Workers are restarted after a part timed out. The parse phase was reported by the first worker already.
+    restarted: bool,
+}
+
+thread_local! {
+    static WORKER: RefCell<Option<Worker>> = const { RefCell::new(None) };
+}
+
+/// Set once a worker was abandoned after a timeout. Its thread keeps running until the process exits.
+static ABANDONED: AtomicBool = AtomicBool::new(false);
+
+type PanicHook = dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static;
+
+/// The number of runs in progress and the panic hook that was installed before the first of them started.
+static QUIET_PANICS: Mutex<(usize, Option<Arc<PanicHook>>)> = Mutex::new((0, None));
+
+/// Keeps the default panic hook from printing panics of worker threads while it is alive.
+/// These panics are reported as the error of their part, the message and backtrace of the hook would only
+/// clutter the output and add to the duration of the part. Panics of other threads still reach the previous hook.
+struct QuietPanics;
+
+impl QuietPanics {
+    fn install() -> Self {
+        let mut state = QUIET_PANICS.lock().unwrap_or_else(|e| e.into_inner());
+
+        if state.0 == 0 {
+            let previous: Arc<PanicHook> = Arc::from(panic::take_hook());
+            state.1 = Some(Arc::clone(&previous));
+
+            panic::set_hook(Box::new(move |info| {
+                let is_worker = WORKER
+                    .try_with(|worker| worker.try_borrow().is_ok_and(|worker| worker.is_some()))
+                    .unwrap_or(false);
+
+                if !is_worker {
+                    previous(info);
+                }
+            }));
+        }
+
+        state.0 += 1;
+        Self
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        let mut state = QUIET_PANICS.lock().unwrap_or_else(|e| e.into_inner());
+        state.0 -= 1;
+
+        if state.0 == 0 {
+            if let Some(previous) = state.1.take() {
+                panic::set_hook(Box::new(move |info| previous(info)));
+            }
+        }
+    }
+}
+
+/// Sends progress to the runner of the current worker thread.
+/// Returns `false` if the runner abandoned this worker, e.g. because it timed out.
+pub fn notify(progress: Progress) -> bool {
+    WORKER.with_borrow(|worker| match worker {
+        Some(worker) => worker.sender.send(progress).is_ok(),
+        // not running on a worker, e.g. in tests.
+        None => true,
+    })
+}
+
+/// Whether the current thread is a worker that was started after a part timed out.
+pub fn is_restarted() -> bool {
+    WORKER.with_borrow(|worker| worker.as_ref().is_some_and(|worker| worker.restarted))
+}
+
+/// Whether a worker was abandoned after a timeout. Its thread competes with everything that runs after it,
+/// so the timings of later parts are unreliable.
+pub fn has_abandoned() -> bool {
+    ABANDONED.load(Ordering::Relaxed)
+}
+
+/// Extracts the message of a panic.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".into())
+}
+
+fn spawn(
+    solution: &'static dyn Solution,
+    input: Arc<str>,
+    options: RunOptions,
+    restarted: bool,
+) -> Receiver<Progress> {
+    let (sender, receiver) = mpsc::channel();
+
+    let worker = move || {
+        WORKER.set(Some(Worker {
+            sender: sender.clone(),
+            restarted,
+        }));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| solution.run(&input, &options)));
+
+        let _ = sender.send(match result {
+            Ok(_) => Progress::Done,
+            Err(payload) => Progress::Panicked(panic_message(&*payload)),
+        });
+    };
+
+    if let Err(e) = thread::Builder::new().stack_size(STACK_SIZE).spawn(worker) {
+        eprintln!("Failed to start a thread for the solution: {e}");
+    }
+
+    receiver
+}
+
+/// Runs a solution on a worker thread and collects its reports.
+/// A part that does not finish its first run within `options.timeout` is reported as `timeout`,
+/// its worker is abandoned and the remaining part continues on a new worker.
+pub fn run(solution: &'static dyn Solution, input: &str, options: &RunOptions) -> Vec<PartReport> {
+    let puzzle = solution.puzzle();
+    let input: Arc<str> = input.into();
+    let mut reports: Vec<PartReport> = vec![];
+    let mut options = options.clone();
+    let mut restarted = false;
+    let _quiet_panics = QuietPanics::install();
+
+    let remaining = |options: &RunOptions, reports: &[PartReport]| -> Vec<Part> {
+        Part::both()
+            .into_iter()
+            .filter(|&part| options.runs(puzzle, part))
+            .filter(|&part| {
+                !reports
+                    .iter()
+                    .any(|report| report.phase == Phase::Part(part))
+            })
+            .collect()
+    };
+
+    'workers: loop {
+        let receiver = spawn(solution, Arc::clone(&input), options.clone(), restarted);
+        // the phase and variant whose first run has to finish before the timeout.
+        let mut running: Option<(Phase, Option<String>, Instant)> = None;
+        let mut phase = Phase::Parse;
+
+        loop {
+            let progress = match (&running, options.timeout) {
+                (Some((_, _, started)), Some(timeout)) => {
+                    receiver.recv_timeout(timeout.saturating_sub(started.elapsed()))
+                }
+                _ => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match progress {
+                Ok(Progress::Started(started)) => {
+                    phase = started;
+                    running = Some((phase, None, Instant::now()));
+                }
+                Ok(Progress::Variant(name)) => running = Some((phase, Some(name), Instant::now())),
+                Ok(Progress::Ran) => running = None,
+                Ok(Progress::Report(report)) => {
+                    match reports.iter_mut().find(|known| known.phase == report.phase) {
+                        // parts with variants report again after each variant.
+                        Some(known) if known.phase != Phase::Parse => *known = *report,
+                        Some(_) => {}
+                        None => reports.push(*report),
+                    }
+                }
+                Ok(Progress::Panicked(message)) => {
+                    for part in remaining(&options, &reports) {
+                        let reason = format!("panicked: {message}");
+                        reports.push(runner::fail_part(puzzle, part, &reason, &options));
+                    }
+                    break 'workers;
+                }
+                Ok(Progress::Done) | Err(RecvTimeoutError::Disconnected) => break 'workers,
+                Err(RecvTimeoutError::Timeout) => {
+                    ABANDONED.store(true, Ordering::Relaxed);
+
+                    let Some((Phase::Part(part), variant, _)) = running.take() else {
+                        // parsing timed out, none of the parts can run.
+                        for part in remaining(&options, &reports) {
+                            reports.push(runner::fail_part(puzzle, part, "timeout", &options));
+                        }
+                        break 'workers;
+                    };
+
+                    let known = reports
+                        .iter_mut()
+                        .find(|report| report.phase == Phase::Part(part));
+
+                    match (variant, known) {
+                        (Some(variant), Some(report)) => {
+                            runner::fail_variant(report, &variant, "timeout", &options);
+                        }
+                        _ => reports.push(runner::fail_part(puzzle, part, "timeout", &options)),
+                    }
+
+                    match remaining(&options, &reports).first() {
+                        Some(&next) => {
+                            options.part = Some(next);
+                            restarted = true;
+                            continue 'workers;
+                        }
+                        None => break 'workers,
+                    }
+                }
+            }
+        }
+    }
+
+    reports
+}
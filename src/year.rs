@@ -0,0 +1,163 @@
+use crate::template::datetime::DateTime;
+use crate::Day;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The year of an Advent of Code event (i.e. an integer from 2015 onwards).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// The year of the first event.
+    pub const FIRST: u16 = 2015;
+
+    /// Creates a [`Year`] from the provided value if an event could have taken place in it,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < Self::FIRST {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year selected via `--year` or the `AOC_YEAR` environment variable,
+    /// defaults to the year of the latest event.
+    pub fn current() -> Self {
+        env::var("AOC_YEAR")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_else(Self::latest_event)
+    }
+
+    /// Events start in December, so before that the latest event is the one of the previous year.
+    pub fn latest_event() -> Self {
+        let now = DateTime::now();
+        Self(if now.month == 12 {
+            now.year
+        } else {
+            now.year - 1
+        })
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year from {} onwards", Year::FIRST)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A single puzzle, identified by the year of the event and the day.
+///
+/// # Display
+/// This value displays as the year followed by the two digit day.
+///
+/// ```
+/// # use advent_of_code::{PuzzleId, Day, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value from 2015 onwards"
+            ),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn parses_year() {
+        assert_eq!("2023".parse::<Year>().unwrap(), year!(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn displays_puzzle_id() {
+        assert_eq!(PuzzleId::new(year!(2015), day!(1)).to_string(), "2015-01");
+    }
+
+    #[test]
+    fn latest_event_is_plausible() {
+        assert!(Year::latest_event() >= year!(2023));
+    }
+}